@@ -0,0 +1,303 @@
+//! One-dimensional barcode encoders
+//!
+//! Encoders return the modules from left to right, `true` for a bar, without quiet zones.
+
+use std::fmt;
+
+#[derive(Debug)]
+pub struct BarcodeError(String);
+
+impl fmt::Display for BarcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for BarcodeError {}
+
+/// Bar and space widths of every Code 128 symbol, starting with a bar
+const CODE128_PATTERNS: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+
+const CODE_C: u8 = 99;
+const CODE_B: u8 = 100;
+const START_B: u8 = 104;
+const START_C: u8 = 105;
+const STOP: u8 = 106;
+
+fn widths_to_modules(widths: &str, modules: &mut Vec<bool>) {
+    for (index, width) in widths.bytes().enumerate() {
+        let bar = index % 2 == 0;
+        modules.extend(std::iter::repeat_n(bar, (width - b'0') as usize));
+    }
+}
+
+/// Code 128 for printable ASCII, switching to code set C for runs of digits
+pub fn code128(text: &str) -> Result<Vec<bool>, BarcodeError> {
+    if text.is_empty() {
+        return Err(BarcodeError("the text is empty".to_string()));
+    }
+    if let Some(c) = text.chars().find(|c| !(' '..='~').contains(c)) {
+        return Err(BarcodeError(format!(
+            "`{}` can't be encoded, only printable ASCII is supported",
+            c
+        )));
+    }
+
+    let bytes = text.as_bytes();
+    let digit_run = |start: usize| {
+        bytes[start..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count()
+    };
+
+    let mut values = vec![];
+    let mut in_c = false;
+    let mut index = 0;
+    while index < bytes.len() {
+        let run = digit_run(index);
+        // 숫자 두 개를 한 글자로 묶을 수 있어 길게 이어질 때만 C로 전환
+        let use_c = run >= 4 || (run == bytes.len() && run == 2);
+
+        if use_c {
+            if values.is_empty() {
+                values.push(START_C);
+            } else if !in_c {
+                values.push(CODE_C);
+            }
+            in_c = true;
+
+            let pairs = run / 2;
+            for pair in bytes[index..index + pairs * 2].chunks(2) {
+                values.push((pair[0] - b'0') * 10 + (pair[1] - b'0'));
+            }
+            index += pairs * 2;
+        } else {
+            if values.is_empty() {
+                values.push(START_B);
+            } else if in_c {
+                values.push(CODE_B);
+            }
+            in_c = false;
+
+            values.push(bytes[index] - b' ');
+            index += 1;
+        }
+    }
+
+    let checksum = values
+        .iter()
+        .enumerate()
+        .map(|(position, &value)| position.max(1) * value as usize)
+        .sum::<usize>()
+        % 103;
+    values.push(checksum as u8);
+    values.push(STOP);
+
+    let mut modules = vec![];
+    for value in values {
+        widths_to_modules(CODE128_PATTERNS[value as usize], &mut modules);
+    }
+
+    Ok(modules)
+}
+
+/// Left-hand odd parity codes, the others are derived from these
+const EAN_L: [u8; 10] = [
+    0b0001101, 0b0011001, 0b0010011, 0b0111101, 0b0100011, 0b0110001, 0b0101111, 0b0111011,
+    0b0110111, 0b0001011,
+];
+
+/// Parity of the left-hand digits for every first digit, a set bit is even parity
+const EAN_PARITY: [u8; 10] = [
+    0b000000, 0b001011, 0b001101, 0b001110, 0b010011, 0b011001, 0b011100, 0b010101, 0b010110,
+    0b011010,
+];
+
+fn push_bits(bits: u8, count: u32, modules: &mut Vec<bool>) {
+    for shift in (0..count).rev() {
+        modules.push(bits >> shift & 1 == 1);
+    }
+}
+
+/// Check digit of the first 12 digits of an EAN-13
+fn ean13_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(index, &digit)| digit as u32 * if index % 2 == 0 { 1 } else { 3 })
+        .sum();
+
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// EAN-13 from 12 digits, or 13 digits with a correct check digit
+///
+/// Returns the modules and the full 13 digits.
+pub fn ean13(text: &str) -> Result<(Vec<bool>, String), BarcodeError> {
+    let text: String = text.chars().filter(|c| *c != ' ' && *c != '-').collect();
+    if !text.chars().all(|c| c.is_ascii_digit()) || !(12..=13).contains(&text.len()) {
+        return Err(BarcodeError(
+            "an EAN-13 needs 12 digits, or 13 with the check digit".to_string(),
+        ));
+    }
+
+    let mut digits: Vec<u8> = text.bytes().map(|byte| byte - b'0').collect();
+    let check = ean13_check_digit(&digits[..12]);
+    match digits.get(12) {
+        Some(&given) if given != check => {
+            return Err(BarcodeError(format!(
+                "the check digit should be {}, not {}",
+                check, given
+            )));
+        }
+        Some(_) => {}
+        None => digits.push(check),
+    }
+
+    let mut modules = vec![];
+    push_bits(0b101, 3, &mut modules);
+    let parity = EAN_PARITY[digits[0] as usize];
+    for (index, &digit) in digits[1..7].iter().enumerate() {
+        let l = EAN_L[digit as usize];
+        let even = parity >> (5 - index) & 1 == 1;
+        // G 코드는 R 코드(L의 반전)를 좌우로 뒤집은 것
+        let code = if even {
+            (!l & 0x7f).reverse_bits() >> 1
+        } else {
+            l
+        };
+        push_bits(code, 7, &mut modules);
+    }
+    push_bits(0b01010, 5, &mut modules);
+    for &digit in &digits[7..] {
+        push_bits(!EAN_L[digit as usize] & 0x7f, 7, &mut modules);
+    }
+    push_bits(0b101, 3, &mut modules);
+
+    let digits = digits.iter().map(|digit| (b'0' + digit) as char).collect();
+    Ok((modules, digits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(modules: &[bool]) -> String {
+        modules
+            .iter()
+            .map(|&bar| if bar { '1' } else { '0' })
+            .collect()
+    }
+
+    /// Symbol values read back from Code 128 modules, 11 per symbol and 13 for the stop
+    fn code128_values(modules: &[bool]) -> Vec<u8> {
+        let patterns: Vec<String> = CODE128_PATTERNS
+            .iter()
+            .map(|widths| {
+                let mut modules = vec![];
+                widths_to_modules(widths, &mut modules);
+                bits(&modules)
+            })
+            .collect();
+        let text = bits(modules);
+        assert_eq!((text.len() - 13) % 11, 0);
+
+        let mut values: Vec<u8> = text[..text.len() - 13]
+            .as_bytes()
+            .chunks(11)
+            .map(|symbol| {
+                let symbol = std::str::from_utf8(symbol).unwrap();
+                patterns
+                    .iter()
+                    .position(|pattern| pattern == symbol)
+                    .unwrap() as u8
+            })
+            .collect();
+        assert_eq!(&text[text.len() - 13..], patterns[STOP as usize]);
+        values.push(STOP);
+        values
+    }
+
+    #[test]
+    fn ean13_known_code() {
+        let (modules, digits) = ean13("400638133393").unwrap();
+        assert_eq!(digits, "4006381333931");
+        // 첫 자리 4의 왼쪽 패리티는 LGLLGG
+        assert_eq!(
+            bits(&modules),
+            "101\
+             0001101 0100111 0101111 0111101 0001001 0110011\
+             01010\
+             1000010 1000010 1000010 1110100 1000010 1100110\
+             101"
+            .replace(' ', "")
+        );
+
+        assert_eq!(ean13("4006381333931").unwrap().1, "4006381333931");
+        assert_eq!(ean13("400-638 133393").unwrap().1, "4006381333931");
+    }
+
+    #[test]
+    fn ean13_check_digits() {
+        assert_eq!(ean13("978030640615").unwrap().1, "9780306406157");
+        assert_eq!(ean13("590123412345").unwrap().1, "5901234123457");
+        assert_eq!(ean13("000000000000").unwrap().1, "0000000000000");
+    }
+
+    #[test]
+    fn ean13_rejects_bad_input() {
+        for text in [
+            "",
+            "40063813339",
+            "40063813339312",
+            "40063813339a",
+            "4006381333932",
+            "４００６３８１３３３９３",
+        ] {
+            assert!(ean13(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn code128_checksums() {
+        // 체크섬은 시작 값에 각 값과 위치를 곱한 합을 더한 것의 103 나머지
+        assert_eq!(
+            code128_values(&code128("Hi").unwrap()),
+            [104, 40, 73, 84, 106]
+        );
+        assert_eq!(
+            code128_values(&code128("1234").unwrap()),
+            [105, 12, 34, 82, 106]
+        );
+        assert_eq!(code128_values(&code128("12").unwrap()), [105, 12, 14, 106]);
+        assert_eq!(
+            code128_values(&code128("A12345").unwrap()),
+            [104, 33, 99, 12, 34, 100, 21, 0, 106]
+        );
+        assert_eq!(
+            code128_values(&code128("PJJ123C").unwrap()),
+            [104, 48, 42, 42, 17, 18, 19, 35, 55, 106]
+        );
+    }
+
+    #[test]
+    fn code128_rejects_bad_input() {
+        for text in ["", "tab\there", "é", "line\n"] {
+            assert!(code128(text).is_err(), "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use arcum::braille::{self, DEFAULT_CHARSET, DEFAULT_WIDTH};
+use arcum::framepack::FramePackWriter;
+use arcum::frames::{self, Frame, VideoFrames};
+use image::ImageResult;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "Convert video frames to text frames for the badapple command
+
+Usage: arcum-frames <INPUT> [OPTIONS]
+
+INPUT is a directory of frame images, an animated GIF or a video (requires ffmpeg)
+
+Options:
+  -o, --output <DIR>       Directory to write frameN.txt files to [default: output]
+  -p, --pack <FILE>        Write a single frame pack instead of frameN.txt files
+  -w, --width <N>          Number of characters per line [default: 60]
+  -c, --charset <CHARS>    Characters from the darkest to the brightest pixel
+  -s, --stride <N>         Keep one frame out of every N [default: 1]
+      --fps <N>            Frame rate of a frame directory [default: 30], or to resample a
+                           video to [default: the video's own rate]
+  -h, --help               Print this help";
+
+struct Options {
+    input: PathBuf,
+    output: PathBuf,
+    pack: Option<PathBuf>,
+    width: u32,
+    charset: Vec<char>,
+    stride: usize,
+    fps: Option<u32>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut input = None;
+    let mut options = Options {
+        input: PathBuf::new(),
+        output: PathBuf::from("output"),
+        pack: None,
+        width: DEFAULT_WIDTH,
+        charset: DEFAULT_CHARSET.chars().collect(),
+        stride: 1,
+        fps: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} needs a value", name));
+
+        match arg.as_str() {
+            "-o" | "--output" => options.output = PathBuf::from(value(&arg)?),
+            "-p" | "--pack" => options.pack = Some(PathBuf::from(value(&arg)?)),
+            "-w" | "--width" => {
+                options.width = value(&arg)?
+                    .parse()
+                    .ok()
+                    .filter(|&width| width > 0 && width <= u16::MAX as u32)
+                    .ok_or("the width must be between 1 and 65535")?;
+            }
+            "-c" | "--charset" => {
+                options.charset = value(&arg)?.chars().collect();
+                if options.charset.is_empty() || options.charset.len() > 256 {
+                    return Err("the charset must have 1 to 256 characters".to_string());
+                }
+            }
+            "-s" | "--stride" => {
+                options.stride = value(&arg)?
+                    .parse()
+                    .ok()
+                    .filter(|&stride| stride > 0)
+                    .ok_or("the stride must be a positive number")?;
+            }
+            "--fps" => {
+                options.fps = Some(
+                    value(&arg)?
+                        .parse()
+                        .ok()
+                        .filter(|&fps| fps > 0)
+                        .ok_or("the fps must be a positive number")?,
+                );
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}", arg)),
+            _ if input.is_none() => input = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    options.input = input.ok_or("missing INPUT")?;
+
+    Ok(options)
+}
+
+fn convert(options: &Options) -> Result<usize, Box<dyn std::error::Error>> {
+    let input = options.input.as_path();
+    let is_gif = input
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gif"));
+
+    let gif_data;
+    let frames: Box<dyn Iterator<Item = ImageResult<Frame>>> = if input.is_dir() {
+        let fps = options.fps.unwrap_or(30);
+        Box::new(frames::dir_frames(input, Duration::from_secs(1) / fps)?)
+    } else if is_gif {
+        gif_data = std::fs::read(input)?;
+        Box::new(frames::gif_frames(&gif_data)?)
+    } else {
+        let scale_width = options.width * 4;
+        Box::new(VideoFrames::from_path(
+            input,
+            options.fps,
+            scale_width,
+            None,
+        )?)
+    };
+
+    if options.pack.is_none() {
+        std::fs::create_dir_all(&options.output)?;
+    }
+
+    let mut pack: Option<FramePackWriter> = None;
+    let mut length = Duration::ZERO;
+    let mut written = 0;
+    for (i, frame) in frames.enumerate().step_by(options.stride) {
+        let frame = frame?;
+        length += frame.delay * options.stride as u32;
+
+        if options.pack.is_some() {
+            let cells = braille::cells(&frame.image, options.width, options.charset.len());
+            let pack = pack.get_or_insert_with(|| {
+                let height = cells.len() / options.width as usize;
+                FramePackWriter::new(options.width as u16, height as u16, 0.0, &options.charset)
+            });
+            pack.push(&cells)?;
+        } else {
+            let text = braille::render(&frame.image, options.width, &options.charset);
+            write_frame(&options.output, i, &text)?;
+        }
+        written += 1;
+    }
+
+    if let (Some(path), Some(mut pack)) = (&options.pack, pack) {
+        pack.set_fps(written as f32 / length.as_secs_f32());
+
+        let mut file = BufWriter::new(File::create(path)?);
+        pack.write_to(&mut file)?;
+        file.flush()?;
+    }
+
+    Ok(written)
+}
+
+/// Frames keep the index of the source frame so `badapple` can skip frames by index
+fn write_frame(output: &Path, index: usize, text: &str) -> std::io::Result<()> {
+    std::fs::write(output.join(format!("frame{}.txt", index)), text)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let options = match parse_args(args.into_iter()) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match convert(&options) {
+        Ok(written) => {
+            let output = options.pack.as_ref().unwrap_or(&options.output);
+            println!("Wrote {} frames to {}", written, output.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
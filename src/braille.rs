@@ -0,0 +1,62 @@
+use image::{imageops::FilterType, DynamicImage};
+
+/// Characters used for each brightness level, from the darkest pixel to the brightest one
+pub const DEFAULT_CHARSET: &str = "⣿⣾⣫⣪⣩⡶⠶⠖⠆⠄⠀";
+
+/// Number of characters per line used for Bad Apple!!
+pub const DEFAULT_WIDTH: u32 = 60;
+
+/// Maximum number of characters in a Discord message
+pub const MESSAGE_LIMIT: usize = 2000;
+
+/// Number of text lines needed to keep the aspect ratio of the image
+/// (a character cell is about twice as tall as it is wide)
+pub fn height_for(image_width: u32, image_height: u32, width: u32) -> u32 {
+    let aspect_ratio = image_height as f64 / image_width.max(1) as f64;
+    ((aspect_ratio * width as f64 / 2.0) as u32).max(1)
+}
+
+/// Largest width up to `max_width` whose rendered text fits in `limit` characters
+pub fn fit_width(image_width: u32, image_height: u32, max_width: u32, limit: usize) -> u32 {
+    let mut width = max_width.max(1);
+    while width > 1 {
+        let height = height_for(image_width, image_height, width) as usize;
+        // Newlines count towards the limit too
+        if height * width as usize + height - 1 <= limit {
+            break;
+        }
+        width -= 1;
+    }
+    width
+}
+
+/// Brightness level of every cell, row by row, as indices into a charset of `levels` characters
+/// (at most 256)
+pub fn cells(image: &DynamicImage, width: u32, levels: usize) -> Vec<u8> {
+    let height = height_for(image.width(), image.height(), width);
+    let gray = image
+        .resize_exact(width, height, FilterType::Triangle)
+        .into_luma8();
+
+    gray.pixels()
+        .map(|pixel| (pixel[0] as usize * levels / 256) as u8)
+        .collect()
+}
+
+/// Join cells into rows of `width` characters
+pub fn to_text(cells: &[u8], width: u32, charset: &[char]) -> String {
+    let mut text = String::with_capacity(cells.len() * 4);
+    for (y, row) in cells.chunks(width.max(1) as usize).enumerate() {
+        if y > 0 {
+            text.push('\n');
+        }
+        text.extend(row.iter().map(|&cell| charset[cell as usize]));
+    }
+
+    text
+}
+
+/// Convert an image to rows of characters, one character per cell
+pub fn render(image: &DynamicImage, width: u32, charset: &[char]) -> String {
+    to_text(&cells(image, width, charset.len()), width, charset)
+}
@@ -0,0 +1,704 @@
+use image::Rgba;
+use std::fmt;
+use std::str::FromStr;
+
+/// An sRGB color with alpha
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+#[derive(Debug)]
+pub struct ParseColorError(String);
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+impl ParseColorError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        ParseColorError(message.into())
+    }
+}
+
+fn error(message: impl Into<String>) -> ParseColorError {
+    ParseColorError::new(message)
+}
+
+/// Formats accepted by [`Color::parse`], to show along with parsing errors
+pub const FORMATS: &str = "#RGB, #RRGGBB, #RRGGBBAA, rgb(255, 191, 152), hsl(22, 100%, 80%), \
+hsv(22, 40%, 100%), cmyk(0%, 25%, 40%, 0%) or a color name like coral";
+
+impl Color {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b, a: 255 }
+    }
+
+    /// Parse a hex code (`#` is optional), a CSS-like function or a CSS/X11 color name
+    pub fn parse(input: &str) -> Result<Self, ParseColorError> {
+        let input = input.trim();
+        let lower = input.to_lowercase();
+
+        if let Some((function, arguments)) = lower
+            .strip_suffix(')')
+            .and_then(|rest| rest.split_once('('))
+        {
+            return Self::parse_function(function.trim(), arguments);
+        }
+
+        if let Some(color) = named(&lower) {
+            return Ok(color);
+        }
+
+        Self::parse_hex(lower.strip_prefix('#').unwrap_or(&lower))
+    }
+
+    fn parse_hex(hex: &str) -> Result<Self, ParseColorError> {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error(format!(
+                "`{}` is not a hex code or a color name",
+                hex
+            )));
+        }
+
+        let digits: Vec<u8> = hex.chars().map(|c| c.to_digit(16).unwrap() as u8).collect();
+        let pairs = |digits: &[u8]| -> Vec<u8> {
+            digits
+                .chunks(2)
+                .map(|pair| pair[0] * 16 + pair[1])
+                .collect()
+        };
+
+        let channels = match digits.len() {
+            // 한 자리는 같은 숫자를 두 번 쓴 것과 같음 (#fb9 = #ffbb99)
+            3 | 4 => digits.iter().map(|&d| d * 17).collect(),
+            6 | 8 => pairs(&digits),
+            _ => return Err(error("a hex code must have 3, 4, 6 or 8 digits")),
+        };
+
+        Ok(Color {
+            r: channels[0],
+            g: channels[1],
+            b: channels[2],
+            a: channels.get(3).copied().unwrap_or(255),
+        })
+    }
+
+    fn parse_function(function: &str, arguments: &str) -> Result<Self, ParseColorError> {
+        // 쉼표, 공백, CSS의 `/ alpha` 구분자를 모두 허용
+        let arguments: Vec<&str> = arguments
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|argument| !argument.is_empty())
+            .collect();
+
+        let expected = match function {
+            "cmyk" => 4,
+            _ => 3,
+        };
+        if arguments.len() != expected && arguments.len() != expected + 1 {
+            return Err(error(format!("{}() needs {} values", function, expected)));
+        }
+
+        let alpha = match arguments.get(expected) {
+            Some(alpha) => (fraction(alpha)? * 255.0).round() as u8,
+            None => 255,
+        };
+
+        let color = match function {
+            "rgb" | "rgba" => {
+                let channel = |argument: &str| -> Result<u8, ParseColorError> {
+                    let value = match argument.strip_suffix('%') {
+                        Some(percent) => number(percent)? / 100.0 * 255.0,
+                        None => number(argument)?,
+                    };
+                    Ok(value.round().clamp(0.0, 255.0) as u8)
+                };
+                Color::rgb(
+                    channel(arguments[0])?,
+                    channel(arguments[1])?,
+                    channel(arguments[2])?,
+                )
+            }
+            "hsl" | "hsla" => Color::from_hsl(
+                hue(arguments[0])?,
+                percent(arguments[1])?,
+                percent(arguments[2])?,
+            ),
+            "hsv" | "hsva" | "hsb" | "hsba" => Color::from_hsv(
+                hue(arguments[0])?,
+                percent(arguments[1])?,
+                percent(arguments[2])?,
+            ),
+            "cmyk" => Color::from_cmyk(
+                percent(arguments[0])?,
+                percent(arguments[1])?,
+                percent(arguments[2])?,
+                percent(arguments[3])?,
+            ),
+            _ => return Err(error(format!("unknown color function {}()", function))),
+        };
+
+        Ok(Color { a: alpha, ..color })
+    }
+
+    /// Hue in degrees, saturation and lightness in [0, 1]
+    pub fn from_hsl(h: f64, s: f64, l: f64) -> Self {
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        Self::from_hue_chroma(h, c, l - c / 2.0)
+    }
+
+    /// Hue in degrees, saturation and value in [0, 1]
+    pub fn from_hsv(h: f64, s: f64, v: f64) -> Self {
+        let c = v * s;
+        Self::from_hue_chroma(h, c, v - c)
+    }
+
+    fn from_hue_chroma(h: f64, c: f64, m: f64) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+
+        Self::from_unit(r + m, g + m, b + m)
+    }
+
+    /// Every component in [0, 1]
+    pub fn from_cmyk(c: f64, m: f64, y: f64, k: f64) -> Self {
+        Self::from_unit(
+            (1.0 - c) * (1.0 - k),
+            (1.0 - m) * (1.0 - k),
+            (1.0 - y) * (1.0 - k),
+        )
+    }
+
+    /// Channels in [0, 1], clamped
+    pub fn from_unit(r: f64, g: f64, b: f64) -> Self {
+        let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Color::rgb(channel(r), channel(g), channel(b))
+    }
+
+    /// Channels in [0, 1]
+    pub fn to_unit(self) -> (f64, f64, f64) {
+        (
+            self.r as f64 / 255.0,
+            self.g as f64 / 255.0,
+            self.b as f64 / 255.0,
+        )
+    }
+
+    fn hue_chroma(self) -> (f64, f64, f64, f64) {
+        let (r, g, b) = self.to_unit();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let c = max - min;
+
+        let h = if c == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / c).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / c + 2.0)
+        } else {
+            60.0 * ((r - g) / c + 4.0)
+        };
+
+        (h, c, max, min)
+    }
+
+    /// Hue in degrees, saturation and lightness in [0, 1]
+    pub fn to_hsl(self) -> (f64, f64, f64) {
+        let (h, c, max, min) = self.hue_chroma();
+        let l = (max + min) / 2.0;
+        let s = if c == 0.0 {
+            0.0
+        } else {
+            c / (1.0 - (2.0 * l - 1.0).abs())
+        };
+
+        (h, s, l)
+    }
+
+    /// Hue in degrees, saturation and value in [0, 1]
+    pub fn to_hsv(self) -> (f64, f64, f64) {
+        let (h, c, max, _) = self.hue_chroma();
+        let s = if max == 0.0 { 0.0 } else { c / max };
+
+        (h, s, max)
+    }
+
+    /// Every component in [0, 1]
+    pub fn to_cmyk(self) -> (f64, f64, f64, f64) {
+        let (r, g, b) = self.to_unit();
+        let k = 1.0 - r.max(g).max(b);
+        if k >= 1.0 {
+            return (0.0, 0.0, 0.0, 1.0);
+        }
+
+        (
+            (1.0 - r - k) / (1.0 - k),
+            (1.0 - g - k) / (1.0 - k),
+            (1.0 - b - k) / (1.0 - k),
+            k,
+        )
+    }
+
+    /// Relative luminance as defined by WCAG, 0 for black and 1 for white
+    pub fn luminance(self) -> f64 {
+        let (r, g, b) = self.to_unit();
+        0.2126 * to_linear(r) + 0.7152 * to_linear(g) + 0.0722 * to_linear(b)
+    }
+
+    /// Black or white, whichever is more readable on top of this color
+    pub fn text_color(self) -> Color {
+        if self.luminance() > 0.179 {
+            Color::rgb(0, 0, 0)
+        } else {
+            Color::rgb(255, 255, 255)
+        }
+    }
+
+    /// WCAG contrast ratio between two opaque colors, from 1 to 21
+    pub fn contrast_ratio(self, other: Color) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// This color drawn over an opaque background
+    pub fn over(self, background: Color) -> Color {
+        let alpha = self.a as f64 / 255.0;
+        let blend = |top: u8, bottom: u8| {
+            (top as f64 * alpha + bottom as f64 * (1.0 - alpha)).round() as u8
+        };
+
+        Color::rgb(
+            blend(self.r, background.r),
+            blend(self.g, background.g),
+            blend(self.b, background.b),
+        )
+    }
+
+    /// OKLab coordinates, where distances match perceived differences
+    pub fn to_oklab(self) -> (f64, f64, f64) {
+        let (r, g, b) = self.to_unit();
+        let (r, g, b) = (to_linear(r), to_linear(g), to_linear(b));
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        (
+            0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        )
+    }
+
+    /// From OKLab coordinates, clamped to the sRGB gamut
+    pub fn from_oklab(l: f64, a: f64, b: f64) -> Self {
+        let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+        Self::from_unit(
+            from_linear(4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_),
+            from_linear(-1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_),
+            from_linear(-0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_),
+        )
+    }
+
+    /// `#rrggbb`, or `#rrggbbaa` for translucent colors
+    pub fn to_hex(self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        }
+    }
+
+    pub fn to_rgb_string(self) -> String {
+        if self.a == 255 {
+            format!("rgb({}, {}, {})", self.r, self.g, self.b)
+        } else {
+            format!(
+                "rgba({}, {}, {}, {})",
+                self.r,
+                self.g,
+                self.b,
+                round(self.a as f64 / 255.0, 2)
+            )
+        }
+    }
+
+    pub fn to_hsl_string(self) -> String {
+        let (h, s, l) = self.to_hsl();
+        format!(
+            "hsl({}, {}%, {}%)",
+            round(h, 1),
+            round(s * 100.0, 1),
+            round(l * 100.0, 1)
+        )
+    }
+
+    pub fn to_hsv_string(self) -> String {
+        let (h, s, v) = self.to_hsv();
+        format!(
+            "hsv({}, {}%, {}%)",
+            round(h, 1),
+            round(s * 100.0, 1),
+            round(v * 100.0, 1)
+        )
+    }
+
+    pub fn to_cmyk_string(self) -> String {
+        let (c, m, y, k) = self.to_cmyk();
+        format!(
+            "cmyk({}%, {}%, {}%, {}%)",
+            round(c * 100.0, 1),
+            round(m * 100.0, 1),
+            round(y * 100.0, 1),
+            round(k * 100.0, 1)
+        )
+    }
+
+    /// The named color closest to this one, by OKLab distance
+    pub fn nearest_name(self) -> (&'static str, Color) {
+        let (l, a, b) = self.to_oklab();
+
+        NAMED_COLORS
+            .iter()
+            .map(|&(name, hex)| (name, Color::from(hex)))
+            .min_by(|(_, x), (_, y)| {
+                let distance = |color: &Color| {
+                    let (cl, ca, cb) = color.to_oklab();
+                    (cl - l).powi(2) + (ca - a).powi(2) + (cb - b).powi(2)
+                };
+                distance(x).total_cmp(&distance(y))
+            })
+            .unwrap()
+    }
+}
+
+/// Color vision deficiencies that can be simulated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl Deficiency {
+    /// Machado, Oliveira and Fernandes (2009) matrices at full severity, for linear RGB
+    fn matrix(self) -> [[f64; 3]; 3] {
+        match self {
+            Deficiency::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Deficiency::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Deficiency::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+
+    /// How the color looks to someone with this deficiency, keeping its alpha
+    pub fn simulate(self, color: Color) -> Color {
+        let (r, g, b) = color.to_unit();
+        let linear = [to_linear(r), to_linear(g), to_linear(b)];
+        let [r, g, b] = self.matrix().map(|row| {
+            from_linear(
+                (0..3)
+                    .map(|i| row[i] * linear[i])
+                    .sum::<f64>()
+                    .clamp(0.0, 1.0),
+            )
+        });
+
+        Color {
+            a: color.a,
+            ..Color::from_unit(r, g, b)
+        }
+    }
+}
+
+impl From<u32> for Color {
+    /// From `0xRRGGBB`
+    fn from(hex: u32) -> Self {
+        Color::rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+    }
+}
+
+impl From<Color> for Rgba<u8> {
+    fn from(color: Color) -> Self {
+        Rgba([color.r, color.g, color.b, color.a])
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Color::parse(s)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// sRGB channel in [0, 1] to linear light
+pub fn to_linear(channel: f64) -> f64 {
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Linear light in [0, 1] to an sRGB channel
+pub fn from_linear(channel: f64) -> f64 {
+    if channel <= 0.0031308 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn round(value: f64, digits: i32) -> f64 {
+    let scale = 10f64.powi(digits);
+    (value * scale).round() / scale
+}
+
+fn number(argument: &str) -> Result<f64, ParseColorError> {
+    argument
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| error(format!("`{}` is not a number", argument)))
+}
+
+/// `50%` or `50`, both meaning one half
+fn percent(argument: &str) -> Result<f64, ParseColorError> {
+    let value = number(argument.strip_suffix('%').unwrap_or(argument))?;
+    Ok((value / 100.0).clamp(0.0, 1.0))
+}
+
+/// `0.5` or `50%`
+fn fraction(argument: &str) -> Result<f64, ParseColorError> {
+    match argument.strip_suffix('%') {
+        Some(percent) => Ok((number(percent)? / 100.0).clamp(0.0, 1.0)),
+        None => Ok(number(argument)?.clamp(0.0, 1.0)),
+    }
+}
+
+/// Degrees, with an optional `deg` unit
+fn hue(argument: &str) -> Result<f64, ParseColorError> {
+    number(argument.strip_suffix("deg").unwrap_or(argument))
+}
+
+/// Look up a color name, ignoring case, spaces, dashes and underscores
+pub fn named(name: &str) -> Option<Color> {
+    let name: String = name
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-' && *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    NAMED_COLORS
+        .iter()
+        .chain(X11_COLORS)
+        .find(|&&(candidate, _)| candidate == name)
+        .map(|&(_, hex)| Color::from(hex))
+}
+
+/// CSS named colors
+pub const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
+
+/// X11 colors that are missing from CSS or differ from their CSS namesake
+const X11_COLORS: &[(&str, u32)] = &[
+    ("lightgoldenrod", 0xEEDD82),
+    ("lightslateblue", 0x8470FF),
+    ("navyblue", 0x000080),
+    ("violetred", 0xD02090),
+    ("x11gray", 0xBEBEBE),
+    ("x11grey", 0xBEBEBE),
+    ("x11green", 0x00FF00),
+    ("x11maroon", 0xB03060),
+    ("x11purple", 0xA020F0),
+    ("webgray", 0x808080),
+    ("webgrey", 0x808080),
+    ("webgreen", 0x008000),
+    ("webmaroon", 0x800000),
+    ("webpurple", 0x800080),
+];
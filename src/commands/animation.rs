@@ -0,0 +1,349 @@
+use super::send_error;
+use crate::{Context, Error};
+use arcum::braille::{self, DEFAULT_CHARSET, DEFAULT_WIDTH, MESSAGE_LIMIT};
+use arcum::framepack::FramePack;
+use arcum::frames::{self, Frame, VideoFrames};
+use poise::serenity_prelude::{
+    Attachment, ButtonStyle, ComponentInteraction, ComponentInteractionCollector, CreateActionRow,
+    CreateButton, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use poise::CreateReply;
+use std::sync::Arc;
+use tokio::task;
+use tokio::time::{sleep_until, Duration, Instant};
+use tokio_stream::StreamExt;
+
+/// Minimum time between two edits of the playing message
+/// (Discord allows about five edits every five seconds in a channel)
+const EDIT_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// Playback stops by itself after being paused for this long
+const MAX_PAUSE: Duration = Duration::from_secs(600);
+
+/// Playback stops by itself after this long, so the last edit still happens before
+/// the 15-minute interaction token expires
+const MAX_PLAYBACK: Duration = Duration::from_secs(14 * 60);
+
+/// Largest file accepted by `animate`
+const MAX_FILE_SIZE: u32 = 8 * 1024 * 1024;
+
+/// Largest number of frames kept from an uploaded file
+const MAX_FRAMES: usize = 600;
+
+/// Frames per second sampled from uploaded videos
+const VIDEO_FPS: u32 = 5;
+
+/// Text frames that can be played on a timeline
+pub trait Clip: Sync {
+    /// Index of the frame shown `elapsed` after the start, `None` once the clip is over
+    fn index_at(&self, elapsed: Duration) -> Option<usize>;
+
+    fn text(&self, index: usize) -> Result<String, Error>;
+}
+
+/// Text frames with the time at which each one starts
+pub struct Animation {
+    frames: Vec<String>,
+    starts: Vec<Duration>,
+    length: Duration,
+}
+
+impl Animation {
+    fn from_frames(frames: impl Iterator<Item = Frame>, width: u32) -> Self {
+        let charset: Vec<char> = DEFAULT_CHARSET.chars().collect();
+        let mut animation = Animation {
+            frames: vec![],
+            starts: vec![],
+            length: Duration::ZERO,
+        };
+
+        for frame in frames.take(MAX_FRAMES) {
+            let width = braille::fit_width(
+                frame.image.width(),
+                frame.image.height(),
+                width,
+                MESSAGE_LIMIT,
+            );
+            animation
+                .frames
+                .push(braille::render(&frame.image, width, &charset));
+            animation.starts.push(animation.length);
+            animation.length += frame.delay;
+        }
+
+        animation
+    }
+}
+
+impl Clip for Animation {
+    fn index_at(&self, elapsed: Duration) -> Option<usize> {
+        if elapsed >= self.length {
+            return None;
+        }
+
+        Some(
+            self.starts
+                .partition_point(|&start| start <= elapsed)
+                .saturating_sub(1),
+        )
+    }
+
+    fn text(&self, index: usize) -> Result<String, Error> {
+        Ok(self.frames[index].clone())
+    }
+}
+
+/// Every `stride`-th frame of a frame pack, starting `offset` into the clip
+struct PackClip {
+    pack: Arc<FramePack>,
+    stride: usize,
+    offset: Duration,
+}
+
+impl Clip for PackClip {
+    fn index_at(&self, elapsed: Duration) -> Option<usize> {
+        let position = (elapsed + self.offset).as_secs_f64() * self.pack.fps() as f64;
+        let index = position as usize / self.stride * self.stride;
+
+        (index < self.pack.len()).then_some(index)
+    }
+
+    fn text(&self, index: usize) -> Result<String, Error> {
+        Ok(self.pack.frame(index)?)
+    }
+}
+
+fn controls(id: u64, paused: bool) -> Vec<CreateActionRow> {
+    let toggle = if paused {
+        CreateButton::new(format!("{}resume", id))
+            .label("Resume")
+            .style(ButtonStyle::Success)
+    } else {
+        CreateButton::new(format!("{}pause", id))
+            .label("Pause")
+            .style(ButtonStyle::Primary)
+    };
+    let stop = CreateButton::new(format!("{}stop", id))
+        .label("Stop")
+        .style(ButtonStyle::Danger);
+
+    vec![CreateActionRow::Buttons(vec![toggle, stop])]
+}
+
+/// Play a clip by editing a single message, one playback per channel at a time
+pub async fn play(ctx: Context<'_>, clip: &dyn Clip) -> Result<(), Error> {
+    let channel = ctx.channel_id();
+
+    if !ctx.data().playing_channels.lock().await.insert(channel) {
+        let embed = CreateEmbed::default()
+            .title("Sorry!!")
+            .description("Something is already playing in this channel");
+
+        ctx.send(CreateReply::default().embed(embed)).await?;
+
+        return Ok(());
+    }
+
+    let result = play_with_controls(ctx, clip).await;
+
+    ctx.data().playing_channels.lock().await.remove(&channel);
+
+    result
+}
+
+/// Keep the shown frame in sync with the clock, dropping frames when the edits fall behind
+async fn play_with_controls(ctx: Context<'_>, clip: &dyn Clip) -> Result<(), Error> {
+    let Some(mut shown) = clip.index_at(Duration::ZERO) else {
+        return Ok(());
+    };
+
+    let deadline = Instant::now() + MAX_PLAYBACK;
+    let id = ctx.id();
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .content(clip.text(shown)?)
+                .components(controls(id, false)),
+        )
+        .await?;
+
+    let prefix = id.to_string();
+    let filter_prefix = prefix.clone();
+    let mut presses = ComponentInteractionCollector::new(ctx)
+        .filter(move |press| {
+            press
+                .data
+                .custom_id
+                .strip_prefix(&filter_prefix)
+                .is_some_and(|action| ["pause", "resume", "stop"].contains(&action))
+        })
+        .stream();
+
+    let mut start = Instant::now();
+    let mut paused_at: Option<Instant> = None;
+    let mut next_edit = start + EDIT_INTERVAL;
+
+    loop {
+        tokio::select! {
+            Some(press) = presses.next() => {
+                if press.user.id != ctx.author().id {
+                    // 다른 사람의 버튼 때문에 재생이 끝나지 않도록 오류는 기록만 함
+                    if let Err(e) = reject_press(ctx, &press).await {
+                        println!("Failed to reject a button press: {}", e);
+                    }
+                    continue;
+                }
+
+                match &press.data.custom_id[prefix.len()..] {
+                    "pause" => {
+                        paused_at.get_or_insert_with(Instant::now);
+                    }
+                    "resume" => {
+                        if let Some(paused) = paused_at.take() {
+                            start += paused.elapsed();
+                            next_edit = Instant::now();
+                        }
+                    }
+                    _ => {
+                        press
+                            .create_response(ctx, CreateInteractionResponse::Acknowledge)
+                            .await?;
+                        break;
+                    }
+                }
+
+                let response = CreateInteractionResponseMessage::new()
+                    .components(controls(id, paused_at.is_some()));
+                press
+                    .create_response(ctx, CreateInteractionResponse::UpdateMessage(response))
+                    .await?;
+            }
+            _ = sleep_until(next_edit), if paused_at.is_none() => {
+                next_edit = Instant::now() + EDIT_INTERVAL;
+
+                match clip.index_at(start.elapsed()) {
+                    Some(index) if index != shown => {
+                        reply
+                            .edit(ctx, CreateReply::default().content(clip.text(index)?))
+                            .await?;
+                        shown = index;
+                    }
+                    Some(_) => {}
+                    None => break,
+                }
+            }
+            _ = sleep_until(paused_at.unwrap_or(start) + MAX_PAUSE), if paused_at.is_some() => break,
+            _ = sleep_until(deadline) => break,
+            else => break,
+        }
+    }
+
+    reply
+        .edit(ctx, CreateReply::default().components(vec![]))
+        .await?;
+
+    Ok(())
+}
+
+async fn reject_press(ctx: Context<'_>, press: &ComponentInteraction) -> Result<(), Error> {
+    let response = CreateInteractionResponseMessage::new()
+        .content("Only the person who started the playback can control it")
+        .ephemeral(true);
+
+    press
+        .create_response(ctx, CreateInteractionResponse::Message(response))
+        .await?;
+
+    Ok(())
+}
+
+/// Play Bad Apple!!
+#[poise::command(slash_command)]
+pub async fn badapple(
+    ctx: Context<'_>,
+    #[description = "Number of frames to skip"] frame_count: Option<usize>,
+    #[description = "Second to start playing from"] start: Option<u32>,
+) -> Result<(), Error> {
+    let Some(pack) = ctx.data().badapple.clone() else {
+        return send_error(
+            ctx,
+            "The Bad Apple!! frames are not available".into(),
+            false,
+        )
+        .await;
+    };
+
+    let clip = PackClip {
+        pack,
+        stride: frame_count.unwrap_or(1).max(1),
+        offset: Duration::from_secs(start.unwrap_or(0) as u64),
+    };
+
+    play(ctx, &clip).await
+}
+
+/// Play an uploaded GIF or video as a text animation
+#[poise::command(slash_command)]
+pub async fn animate(
+    ctx: Context<'_>,
+    #[description = "Animated GIF or short video"] file: Attachment,
+    #[description = "Number of characters per line"] width: Option<u32>,
+) -> Result<(), Error> {
+    let width = width.unwrap_or(DEFAULT_WIDTH);
+
+    if !(2..=200).contains(&width) {
+        return send_error(
+            ctx,
+            "The width must be between 2 and 200 inclusive".into(),
+            false,
+        )
+        .await;
+    }
+
+    if file.size > MAX_FILE_SIZE {
+        return send_error(ctx, "The file must be 8 MiB or smaller".into(), false).await;
+    }
+
+    let content_type = file.content_type.clone().unwrap_or_default();
+    let is_gif = content_type == "image/gif" || file.filename.to_lowercase().ends_with(".gif");
+    let is_video = content_type.starts_with("video/");
+
+    if !is_gif && !is_video {
+        return send_error(
+            ctx,
+            "Only animated GIFs and videos can be played".into(),
+            false,
+        )
+        .await;
+    }
+
+    ctx.defer().await?;
+
+    let data = file.download().await?;
+
+    // 프레임 디코딩과 텍스트 변환은 백그라운드 스레드에서 처리
+    let animation = task::spawn_blocking(move || -> Result<Animation, String> {
+        if is_gif {
+            let frames = frames::gif_frames(&data).map_err(|e| e.to_string())?;
+            Ok(Animation::from_frames(frames.map_while(Result::ok), width))
+        } else {
+            let frames =
+                VideoFrames::from_bytes(&data, Some(VIDEO_FPS), width * 2, Some(MAX_FRAMES))
+                    .map_err(|e| e.to_string())?;
+            Ok(Animation::from_frames(frames.map_while(Result::ok), width))
+        }
+    })
+    .await?;
+
+    match animation {
+        Ok(animation) if !animation.frames.is_empty() => play(ctx, &animation).await?,
+        Ok(_) => {
+            send_error(ctx, "The file has no frames".into(), false).await?;
+        }
+        Err(e) => {
+            send_error(ctx, format!("Failed to decode the file\n{}", e), false).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,295 @@
+use super::palette::render;
+use super::send_error;
+use crate::{Context, Error};
+use arcum::color::{self, Color, Deficiency};
+use arcum::font;
+use image::imageops::{self, FilterType};
+use image::{Rgba, RgbaImage};
+use poise::serenity_prelude::{Attachment, CreateAttachment, CreateEmbed};
+use poise::{ChoiceParameter, CreateReply};
+use std::io::Cursor;
+use tokio::task;
+
+const MAX_FILE_SIZE: u32 = 8 * 1024 * 1024;
+/// Uploaded images are shrunk to fit in this size
+const MAX_IMAGE_SIZE: u32 = 512;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Simulation {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl Simulation {
+    const ALL: [Simulation; 3] = [
+        Simulation::Protanopia,
+        Simulation::Deuteranopia,
+        Simulation::Tritanopia,
+    ];
+
+    fn deficiency(self) -> Deficiency {
+        match self {
+            Simulation::Protanopia => Deficiency::Protanopia,
+            Simulation::Deuteranopia => Deficiency::Deuteranopia,
+            Simulation::Tritanopia => Deficiency::Tritanopia,
+        }
+    }
+}
+
+fn pass(passed: bool) -> &'static str {
+    if passed {
+        "pass"
+    } else {
+        "fail"
+    }
+}
+
+/// The sample text in the foreground color over the background color
+fn sample(foreground: Color, background: Color, ratio: f64) -> RgbaImage {
+    let lines = [
+        ("Large text", 4),
+        ("Normal text looks like this", 2),
+        (&*format!("Contrast {:.2}:1", ratio), 2),
+    ];
+
+    let width = lines
+        .iter()
+        .map(|(text, scale)| font::text_width(text, *scale))
+        .max()
+        .unwrap()
+        + 40;
+    let height = lines
+        .iter()
+        .map(|(_, scale)| font::text_height(*scale) + 16)
+        .sum::<u32>()
+        + 24;
+
+    let mut img = RgbaImage::from_pixel(width, height, background.into());
+    let mut y = 20;
+    for (text, scale) in lines {
+        font::draw_text(&mut img, 20, y, text, scale, foreground.into());
+        y += (font::text_height(scale) + 16) as i64;
+    }
+
+    img
+}
+
+/// Check the WCAG contrast between a text color and a background color
+#[poise::command(slash_command)]
+pub async fn contrast(
+    ctx: Context<'_>,
+    #[description = "the text color, like hexcode"] foreground: String,
+    #[description = "the background color, like hexcode"] background: String,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let (foreground, background) = match (Color::parse(&foreground), Color::parse(&background)) {
+        (Ok(foreground), Ok(background)) => {
+            // 반투명한 색은 실제로 보이는 색으로 합성해서 비교
+            let background = background.over(Color::rgb(255, 255, 255));
+            (foreground.over(background), background)
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            return send_error(
+                ctx,
+                format!("{}\nAccepted formats : {}", e, color::FORMATS),
+                false,
+            )
+            .await;
+        }
+    };
+
+    let ratio = foreground.contrast_ratio(background);
+
+    let mut buffer = Vec::new();
+    sample(foreground, background, ratio)
+        .write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
+
+    let attachment = CreateAttachment::bytes(&*buffer, "contrast.png");
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(format!(
+            "foreground : {}\nbackground : {}\ncontrast ratio : {:.2}:1\n\nnormal text AA (4.5:1) : {}\nnormal text AAA (7:1) : {}\nlarge text AA (3:1) : {}\nlarge text AAA (4.5:1) : {}\nui components AA (3:1) : {}",
+            foreground,
+            background,
+            ratio,
+            pass(ratio >= 4.5),
+            pass(ratio >= 7.0),
+            pass(ratio >= 3.0),
+            pass(ratio >= 4.5),
+            pass(ratio >= 3.0)
+        ));
+
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
+
+    Ok(())
+}
+
+fn simulate_image(img: &RgbaImage, deficiency: Deficiency) -> RgbaImage {
+    RgbaImage::from_fn(img.width(), img.height(), |x, y| {
+        let pixel = img.get_pixel(x, y);
+        let color = Color {
+            r: pixel[0],
+            g: pixel[1],
+            b: pixel[2],
+            a: pixel[3],
+        };
+        deficiency.simulate(color).into()
+    })
+}
+
+/// Every image in a 2×2 grid, labeled in its top left corner
+fn grid(images: &[(&str, RgbaImage)]) -> RgbaImage {
+    let (width, height) = images[0].1.dimensions();
+    let mut img = RgbaImage::new(width * 2, height * 2);
+
+    for (index, (label, image)) in images.iter().enumerate() {
+        let left = (index as u32 % 2) * width;
+        let top = (index as u32 / 2) * height;
+        imageops::replace(&mut img, image, left as i64, top as i64);
+
+        let label_width = font::text_width(label, 2) + 12;
+        let label_height = font::text_height(2) + 12;
+        for y in 0..label_height.min(height) {
+            for x in 0..label_width.min(width) {
+                img.put_pixel(left + x, top + y, Rgba([255, 255, 255, 220]));
+            }
+        }
+        font::draw_text(
+            &mut img,
+            left as i64 + 6,
+            top as i64 + 6,
+            label,
+            2,
+            Rgba([0, 0, 0, 255]),
+        );
+    }
+
+    img
+}
+
+/// Simulate color blindness on a color or an image
+#[poise::command(slash_command)]
+pub async fn colorblind(
+    ctx: Context<'_>,
+    #[description = "the color, like hexcode"] color: Option<String>,
+    #[description = "the image"] file: Option<Attachment>,
+    #[description = "the color vision deficiency (every one if empty)"] kind: Option<Simulation>,
+) -> Result<(), Error> {
+    let simulations = match kind {
+        Some(kind) => vec![kind],
+        None => Simulation::ALL.to_vec(),
+    };
+
+    let error = match (&color, &file) {
+        (Some(_), Some(_)) | (None, None) => Some("Give either a color or an image"),
+        (_, Some(file)) if file.size > MAX_FILE_SIZE => Some("The file must be 8 MiB or smaller"),
+        _ => None,
+    };
+
+    if let Some(error) = error {
+        return send_error(ctx, error.into(), false).await;
+    }
+
+    ctx.defer().await?;
+
+    if let Some(color) = color {
+        let color = match Color::parse(&color) {
+            Ok(color) => Color { a: 255, ..color },
+            Err(e) => {
+                return send_error(
+                    ctx,
+                    format!("{}\nAccepted formats : {}", e, color::FORMATS),
+                    false,
+                )
+                .await;
+            }
+        };
+
+        let rows: Vec<(String, Vec<(Color, String)>)> = [("Original".to_string(), color)]
+            .into_iter()
+            .chain(simulations.iter().map(|simulation| {
+                let simulated = simulation.deficiency().simulate(color);
+                (simulation.name().to_string(), simulated)
+            }))
+            .map(|(name, color)| (name, vec![(color, color.to_hex())]))
+            .collect();
+
+        let description: String = rows
+            .iter()
+            .map(|(name, colors)| format!("{} : {}\n", name.to_lowercase(), colors[0].0))
+            .collect();
+
+        let img = task::spawn_blocking(move || render(&rows)).await.unwrap();
+
+        let mut buffer = Vec::new();
+        img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
+
+        let attachment = CreateAttachment::bytes(&*buffer, "colorblind.png");
+
+        let embed = CreateEmbed::default()
+            .title("Generated!!")
+            .description(description);
+
+        ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+            .await?;
+
+        return Ok(());
+    }
+
+    let data = file.unwrap().download().await?;
+    let names: Vec<&str> = simulations
+        .iter()
+        .map(|simulation| simulation.name())
+        .collect();
+
+    // 디코딩과 시뮬레이션은 백그라운드 스레드에서 처리
+    let img = task::spawn_blocking(move || -> Result<RgbaImage, image::ImageError> {
+        let original = image::load_from_memory(&data)?;
+        let original = if original.width() > MAX_IMAGE_SIZE || original.height() > MAX_IMAGE_SIZE {
+            original.resize(MAX_IMAGE_SIZE, MAX_IMAGE_SIZE, FilterType::Triangle)
+        } else {
+            original
+        }
+        .to_rgba8();
+
+        if let [simulation] = simulations[..] {
+            return Ok(simulate_image(&original, simulation.deficiency()));
+        }
+
+        let mut images = vec![("Original", original.clone())];
+        for simulation in &simulations {
+            images.push((
+                simulation.name(),
+                simulate_image(&original, simulation.deficiency()),
+            ));
+        }
+
+        Ok(grid(&images))
+    })
+    .await?;
+
+    let img = match img {
+        Ok(img) => img,
+        Err(e) => {
+            return send_error(ctx, format!("Failed to decode the image\n{}", e), false).await;
+        }
+    };
+
+    let mut buffer = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
+
+    let attachment = CreateAttachment::bytes(&*buffer, "colorblind.png");
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(format!("simulated : {}", names.join(", ").to_lowercase()));
+
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
+
+    Ok(())
+}
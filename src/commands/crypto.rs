@@ -0,0 +1,260 @@
+use super::hash::{read_input, Privacy, MODAL_TIMEOUT};
+use super::{code_block, send_error};
+use crate::{Context, Error};
+use arcum::crypto::{self, KeyParams};
+use data_encoding::HEXLOWER;
+use poise::serenity_prelude::CreateEmbed;
+use poise::{ChoiceParameter, CreateReply};
+use rand::rngs::OsRng;
+use tokio::task;
+
+/// Longest message to encrypt, so the armored result fits in an embed
+const MAX_MESSAGE: usize = 2048;
+/// Longest text shown in a reply, within the embed description limit
+const MAX_SHOWN: usize = 3800;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Cipher {
+    #[name = "ChaCha20-Poly1305"]
+    ChaCha20Poly1305,
+    #[name = "AES-256-GCM"]
+    Aes256Gcm,
+}
+
+impl Cipher {
+    fn cipher(self) -> crypto::Cipher {
+        match self {
+            Cipher::ChaCha20Poly1305 => crypto::Cipher::ChaCha20Poly1305,
+            Cipher::Aes256Gcm => crypto::Cipher::Aes256Gcm,
+        }
+    }
+}
+
+#[derive(Debug, poise::Modal)]
+#[name = "Private input"]
+struct EncryptInput {
+    #[name = "Message to encrypt"]
+    #[paragraph]
+    input: String,
+    #[name = "Passphrase"]
+    passphrase: String,
+}
+
+/// Encrypt a message with a passphrase
+#[poise::command(slash_command)]
+pub async fn encrypt(
+    ctx: Context<'_>,
+    #[description = "Message to encrypt, asked in a private form if empty"] input: Option<String>,
+    #[description = "Passphrase, asked in a private form if empty"] passphrase: Option<String>,
+    #[description = "Cipher (default ChaCha20-Poly1305)"] cipher: Option<Cipher>,
+    #[description = "Show the reply to everyone (default false)"] public: Option<bool>,
+) -> Result<(), Error> {
+    let cipher = cipher.unwrap_or(Cipher::ChaCha20Poly1305);
+    let privacy = Privacy::new(public, None);
+
+    // 폼은 한 번만 띄울 수 있으므로 메시지와 암호를 한 폼에서 받음
+    let (input, passphrase) = match (input, passphrase) {
+        (Some(input), Some(passphrase)) => (input, passphrase),
+        (input, passphrase) => {
+            let Context::Application(ctx) = ctx else {
+                return Ok(());
+            };
+            let defaults = EncryptInput {
+                input: input.unwrap_or_default(),
+                passphrase: passphrase.unwrap_or_default(),
+            };
+            let modal = poise::execute_modal(ctx, Some(defaults), Some(MODAL_TIMEOUT)).await?;
+            let Some(modal) = modal else {
+                return Ok(());
+            };
+            (modal.input, modal.passphrase)
+        }
+    };
+
+    if input.len() > MAX_MESSAGE {
+        return send_error(
+            ctx,
+            format!("The message must be at most {} bytes", MAX_MESSAGE),
+            true,
+        )
+        .await;
+    }
+    if passphrase.is_empty() {
+        return send_error(ctx, "The passphrase is empty".into(), true).await;
+    }
+
+    privacy.defer(ctx).await?;
+
+    let params = KeyParams::default();
+    let encrypted = task::spawn_blocking(move || {
+        crypto::encrypt(
+            &mut OsRng,
+            input.as_bytes(),
+            &passphrase,
+            cipher.cipher(),
+            params,
+        )
+    })
+    .await?;
+
+    let encrypted = match encrypted {
+        Ok(encrypted) => encrypted,
+        Err(e) => return send_error(ctx, e.to_string(), true).await,
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Encrypted!!")
+        .description(format!(
+            "cipher: {}\nkey: argon2id, m={}, t={}, p={}\n{}",
+            cipher.name(),
+            params.memory,
+            params.time,
+            params.parallelism,
+            code_block(&crypto::armor(&encrypted), MAX_SHOWN)
+        ));
+
+    ctx.send(privacy.reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Decrypt a message from /encrypt
+#[poise::command(slash_command)]
+pub async fn decrypt(
+    ctx: Context<'_>,
+    #[description = "Armored message, the BEGIN and END lines can be left out"] message: String,
+    #[description = "Passphrase, asked in a private form if empty"] passphrase: Option<String>,
+    #[description = "Show the reply to everyone (default false)"] public: Option<bool>,
+) -> Result<(), Error> {
+    let privacy = Privacy::new(public, None);
+
+    let encrypted = match crypto::dearmor(&message) {
+        Ok(encrypted) => encrypted,
+        Err(e) => return send_error(ctx, e.to_string(), true).await,
+    };
+
+    let Some(passphrase) = read_input(ctx, passphrase).await? else {
+        return Ok(());
+    };
+
+    privacy.defer(ctx).await?;
+
+    let decrypted = task::spawn_blocking(move || crypto::decrypt(&encrypted, &passphrase)).await?;
+    let (plaintext, decrypted) = match decrypted {
+        Ok(decrypted) => decrypted,
+        Err(e) => return send_error(ctx, e.to_string(), true).await,
+    };
+
+    // 글자가 아닌 바이트는 16진수로 보여줌
+    let shown = match String::from_utf8(plaintext) {
+        Ok(text) => code_block(&text, MAX_SHOWN),
+        Err(e) => format!(
+            "The message is not text, shown in hex\n{}",
+            code_block(&HEXLOWER.encode(e.as_bytes()), MAX_SHOWN)
+        ),
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Decrypted!!")
+        .description(format!(
+            "cipher: {}\nkey: argon2id, m={}, t={}, p={}\n{}",
+            decrypted.cipher.name(),
+            decrypted.params.memory,
+            decrypted.params.time,
+            decrypted.params.parallelism,
+            shown
+        ));
+
+    ctx.send(privacy.reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Make a new Ed25519 key pair for /sign and /verify-signature
+#[poise::command(slash_command)]
+pub async fn keypair(ctx: Context<'_>) -> Result<(), Error> {
+    let key = crypto::generate_signing_key(&mut OsRng);
+
+    // 개인 키는 본인에게만 보여줌
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(format!(
+            "private key, keep it secret\n```\n{}\n```\n\
+             public key, share it so others can check your signatures\n```\n{}\n```",
+            crypto::encode_key(key.as_bytes()),
+            crypto::encode_key(key.verifying_key().as_bytes())
+        ));
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Sign a message with an Ed25519 private key from /keypair
+#[poise::command(slash_command)]
+pub async fn sign(
+    ctx: Context<'_>,
+    #[description = "Message to sign"] input: String,
+    #[description = "Private key, asked in a private form if empty"] private_key: Option<String>,
+    #[description = "Show the reply to everyone (default false)"] public: Option<bool>,
+    #[description = "Leave the input out of the reply (default false)"] hide_input: Option<bool>,
+) -> Result<(), Error> {
+    let privacy = Privacy::new(public, hide_input);
+    let Some(private_key) = read_input(ctx, private_key).await? else {
+        return Ok(());
+    };
+
+    let key = match crypto::parse_signing_key(&private_key) {
+        Ok(key) => key,
+        Err(e) => return send_error(ctx, e.to_string(), true).await,
+    };
+    let signature = crypto::sign(&key, input.as_bytes());
+
+    // 개인 키는 비밀이므로 응답에 보여주지 않음
+    let embed = CreateEmbed::default()
+        .title("Signed!!")
+        .description(format!(
+            "{}\npublic key: {}\nsignature: {}",
+            privacy.input(&input),
+            crypto::encode_key(key.verifying_key().as_bytes()),
+            crypto::encode_key(&signature)
+        ));
+
+    ctx.send(privacy.reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Check an Ed25519 signature from /sign
+#[poise::command(slash_command, rename = "verify-signature")]
+pub async fn verify_signature(
+    ctx: Context<'_>,
+    #[description = "Message that was signed"] input: String,
+    #[description = "Public key of the signer"] public_key: String,
+    #[description = "Signature to check"] signature: String,
+    #[description = "Show the reply to everyone (default false)"] public: Option<bool>,
+    #[description = "Leave the input out of the reply (default false)"] hide_input: Option<bool>,
+) -> Result<(), Error> {
+    let privacy = Privacy::new(public, hide_input);
+
+    let valid = crypto::parse_verifying_key(&public_key)
+        .and_then(|key| crypto::verify(&key, input.as_bytes(), &signature));
+    let valid = match valid {
+        Ok(valid) => valid,
+        Err(e) => return send_error(ctx, e.to_string(), true).await,
+    };
+
+    let embed = CreateEmbed::default()
+        .title(if valid { "Valid!!" } else { "Invalid!!" })
+        .description(format!(
+            "{}\npublic key: {}\nsignature: {}",
+            privacy.input(&input),
+            public_key.trim(),
+            signature.trim()
+        ));
+
+    ctx.send(privacy.reply(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use super::{code_block, send_error, CODE_BLOCK_OVERHEAD, MAX_DESCRIPTION};
+use crate::{Context, Error};
+use arcum::encoding::{self as codec, Codec};
+use data_encoding::HEXLOWER;
+use poise::serenity_prelude::CreateEmbed;
+use poise::CreateReply;
+
+/// Longest text shown in a reply, within the embed description limit
+const MAX_SHOWN: usize = 3800;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Format {
+    #[name = "base64"]
+    Base64,
+    #[name = "base64url"]
+    Base64Url,
+    #[name = "base32"]
+    Base32,
+    #[name = "base58"]
+    Base58,
+    #[name = "hex"]
+    Hex,
+    #[name = "URL percent-encoding"]
+    Percent,
+    #[name = "HTML entities"]
+    Html,
+    #[name = "ROT13"]
+    Rot13,
+    #[name = "Morse code"]
+    Morse,
+}
+
+impl Format {
+    fn codec(self) -> Codec {
+        match self {
+            Format::Base64 => Codec::Base64,
+            Format::Base64Url => Codec::Base64Url,
+            Format::Base32 => Codec::Base32,
+            Format::Base58 => Codec::Base58,
+            Format::Hex => Codec::Hex,
+            Format::Percent => Codec::Percent,
+            Format::Html => Codec::Html,
+            Format::Rot13 => Codec::Rot13,
+            Format::Morse => Codec::Morse,
+        }
+    }
+}
+
+/// Encode text in base64, hex, Morse code and more
+#[poise::command(slash_command)]
+pub async fn encode(
+    ctx: Context<'_>,
+    #[description = "Text to encode"] input: String,
+    #[description = "Encoding to use"] format: Format,
+) -> Result<(), Error> {
+    let encoded = match format.codec().encode(&input) {
+        Ok(encoded) => encoded,
+        Err(e) => return send_error(ctx, e.to_string(), false).await,
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Encoded!!")
+        .description(format!(
+            "format : {}\n{}",
+            format.codec().name(),
+            code_block(&encoded, MAX_SHOWN)
+        ));
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Decode text from base64, hex, Morse code and more, or guess the encoding
+#[poise::command(slash_command)]
+pub async fn decode(
+    ctx: Context<'_>,
+    #[description = "Text to decode"] input: String,
+    #[description = "Encoding to read (default detect it)"] format: Option<Format>,
+) -> Result<(), Error> {
+    let description = match format {
+        Some(format) => {
+            let decoded = match format.codec().decode(&input) {
+                Ok(decoded) => decoded,
+                Err(e) => return send_error(ctx, e.to_string(), false).await,
+            };
+
+            // 글자가 아닌 바이트는 16진수로 보여줌
+            match String::from_utf8(decoded) {
+                Ok(text) => format!(
+                    "format : {}\n{}",
+                    format.codec().name(),
+                    code_block(&text, MAX_SHOWN)
+                ),
+                Err(e) => format!(
+                    "format : {}\nThe result is not text, shown in hex\n{}",
+                    format.codec().name(),
+                    code_block(&HEXLOWER.encode(e.as_bytes()), MAX_SHOWN)
+                ),
+            }
+        }
+        None => {
+            let found = codec::detect(&input);
+            if found.is_empty() {
+                return send_error(ctx, "No encoding gave readable text".into(), false).await;
+            }
+
+            // 임베드 설명 길이 제한에 맞게 제목과 코드 블록 틀을 빼고 남은 길이를 나눔
+            let headings: Vec<String> = found
+                .iter()
+                .map(|(codec, _)| format!("**{}**\n", codec.name()))
+                .collect();
+            let fixed: usize = headings
+                .iter()
+                .map(|heading| heading.chars().count() + CODE_BLOCK_OVERHEAD + 1)
+                .sum();
+            let limit = MAX_DESCRIPTION.saturating_sub(fixed) / found.len();
+
+            headings
+                .iter()
+                .zip(&found)
+                .map(|(heading, (_, text))| format!("{}{}\n", heading, code_block(text, limit)))
+                .collect()
+        }
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Decoded!!")
+        .description(description);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -1,78 +1,824 @@
+use super::send_error;
 use crate::{Context, Error};
+use arcum::hash;
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
     Argon2,
 };
-use poise::serenity_prelude::CreateEmbed;
-use poise::CreateReply;
-use sha2::{Digest, Sha256};
+use pbkdf2::Pbkdf2;
+use poise::serenity_prelude::{Attachment, CreateEmbed};
+use poise::{ChoiceParameter, CreateReply};
+use scrypt::Scrypt;
+use std::time::Duration;
+use tokio::task;
 
-/// Make sha-256 hash value
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Algorithm {
+    #[name = "MD5"]
+    Md5,
+    #[name = "SHA-1"]
+    Sha1,
+    #[name = "SHA-224"]
+    Sha224,
+    #[name = "SHA-256"]
+    Sha256,
+    #[name = "SHA-384"]
+    Sha384,
+    #[name = "SHA-512"]
+    Sha512,
+    #[name = "SHA3-256"]
+    Sha3_256,
+    #[name = "SHA3-512"]
+    Sha3_512,
+    #[name = "BLAKE2b"]
+    Blake2b,
+    #[name = "BLAKE3"]
+    Blake3,
+    #[name = "CRC32"]
+    Crc32,
+    #[name = "xxHash64"]
+    Xxh64,
+    #[name = "XXH3"]
+    Xxh3,
+}
+
+impl Algorithm {
+    fn algorithm(self) -> hash::Algorithm {
+        match self {
+            Algorithm::Md5 => hash::Algorithm::Md5,
+            Algorithm::Sha1 => hash::Algorithm::Sha1,
+            Algorithm::Sha224 => hash::Algorithm::Sha224,
+            Algorithm::Sha256 => hash::Algorithm::Sha256,
+            Algorithm::Sha384 => hash::Algorithm::Sha384,
+            Algorithm::Sha512 => hash::Algorithm::Sha512,
+            Algorithm::Sha3_256 => hash::Algorithm::Sha3_256,
+            Algorithm::Sha3_512 => hash::Algorithm::Sha3_512,
+            Algorithm::Blake2b => hash::Algorithm::Blake2b,
+            Algorithm::Blake3 => hash::Algorithm::Blake3,
+            Algorithm::Crc32 => hash::Algorithm::Crc32,
+            Algorithm::Xxh64 => hash::Algorithm::Xxh64,
+            Algorithm::Xxh3 => hash::Algorithm::Xxh3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Encoding {
+    #[name = "hex"]
+    Hex,
+    #[name = "base64"]
+    Base64,
+    #[name = "base32"]
+    Base32,
+}
+
+impl Encoding {
+    fn encoding(self) -> hash::Encoding {
+        match self {
+            Encoding::Hex => hash::Encoding::Hex,
+            Encoding::Base64 => hash::Encoding::Base64,
+            Encoding::Base32 => hash::Encoding::Base32,
+        }
+    }
+}
+
+/// Largest attachment that is downloaded and hashed
+const MAX_FILE_SIZE: u32 = 100 * 1024 * 1024;
+
+/// How long the private input form waits to be submitted
+pub(crate) const MODAL_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Largest costs accepted by /kdf, /argon2 and /verify, so a hash cannot make the
+/// bot spend minutes or gigabytes
+const MAX_PBKDF2_ROUNDS: u32 = 2_000_000;
+const MAX_SCRYPT_LOG_N: u8 = 17;
+const MAX_SCRYPT_BLOCK_SIZE: u32 = 16;
+const MAX_SCRYPT_PARALLELISM: u32 = 4;
+const MAX_SCRYPT_MEMORY: u64 = 128 * 1024 * 1024;
+const MAX_BCRYPT_COST: u32 = 16;
+const MAX_ARGON2_MEMORY: u32 = 262144;
+const MAX_ARGON2_TIME: u32 = 10;
+const MAX_ARGON2_PARALLELISM: u32 = 8;
+
+#[derive(Debug, poise::Modal)]
+#[name = "Private input"]
+struct PrivateInput {
+    #[name = "Value to change"]
+    #[paragraph]
+    input: String,
+}
+
+#[derive(Debug, poise::Modal)]
+#[name = "Private input"]
+struct HmacInput {
+    #[name = "Secret key"]
+    key: String,
+    #[name = "Message to authenticate"]
+    #[paragraph]
+    input: String,
+}
+
+/// Who sees the reply and whether it repeats the input, replies are only shown to
+/// the user unless they ask otherwise since the input is often a password
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Privacy {
+    ephemeral: bool,
+    hide_input: bool,
+}
+
+impl Privacy {
+    pub(crate) fn new(public: Option<bool>, hide_input: Option<bool>) -> Self {
+        Privacy {
+            ephemeral: !public.unwrap_or(false),
+            hide_input: hide_input.unwrap_or(false),
+        }
+    }
+
+    pub(crate) fn input(self, input: &str) -> String {
+        if self.hide_input {
+            "input: (hidden)".into()
+        } else {
+            format!("input: {}", input)
+        }
+    }
+
+    pub(crate) async fn defer(self, ctx: Context<'_>) -> Result<(), Error> {
+        if self.ephemeral {
+            ctx.defer_ephemeral().await?;
+        } else {
+            ctx.defer().await?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn reply(self, embed: CreateEmbed) -> CreateReply {
+        CreateReply::default()
+            .embed(embed)
+            .ephemeral(self.ephemeral)
+    }
+}
+
+/// The input from the command option, or from a form when the option was left empty
+/// so that it never shows up in the command's options
+///
+/// Returns `None` when the form was not submitted in time.
+pub(crate) async fn read_input(
+    ctx: Context<'_>,
+    input: Option<String>,
+) -> Result<Option<String>, Error> {
+    if input.is_some() {
+        return Ok(input);
+    }
+    let Context::Application(ctx) = ctx else {
+        return Ok(None);
+    };
+
+    let modal = poise::execute_modal(ctx, None::<PrivateInput>, Some(MODAL_TIMEOUT)).await?;
+
+    Ok(modal.map(|modal| modal.input))
+}
+
+/// Feed the attachment to the hasher as it downloads, returning the number of bytes read
+async fn hash_file(file: &Attachment, hasher: &mut hash::Hasher) -> Result<u64, Error> {
+    let mut response = reqwest::get(&file.url).await?.error_for_status()?;
+    let mut size = 0;
+    while let Some(chunk) = response.chunk().await? {
+        size += chunk.len() as u64;
+        hasher.update(&chunk);
+    }
+
+    Ok(size)
+}
+
+async fn send_hash(
+    ctx: Context<'_>,
+    input: Option<String>,
+    file: Option<Attachment>,
+    expected: Option<String>,
+    algorithm: Algorithm,
+    encoding: Encoding,
+    privacy: Privacy,
+) -> Result<(), Error> {
+    let mut hasher = algorithm.algorithm().hasher();
+    let source = match (input, file) {
+        (Some(_), Some(_)) => {
+            return send_error(ctx, "Give either an input or a file, not both".into(), true).await
+        }
+        (None, Some(file)) => {
+            if file.size > MAX_FILE_SIZE {
+                return send_error(ctx, "The file must be 100 MiB or smaller".into(), true).await;
+            }
+
+            privacy.defer(ctx).await?;
+
+            let size = hash_file(&file, &mut hasher).await?;
+            format!("file: {}\nsize: {} bytes", file.filename, size)
+        }
+        (input, None) => {
+            let Some(input) = read_input(ctx, input).await? else {
+                return Ok(());
+            };
+            hasher.update(input.as_bytes());
+            privacy.input(&input)
+        }
+    };
+
+    let digest = hasher.finalize();
+    let hash = encoding.encoding().encode(&digest);
+
+    let mut description = format!(
+        "{}\nalgorithm: {}\nencoding: {}\nhash: {}",
+        source,
+        algorithm.name(),
+        encoding.name(),
+        hash
+    );
+
+    // 기대한 값이 있으면 인코딩에 상관없이 비교
+    let title = match expected {
+        Some(expected) => {
+            description += &format!("\nexpected: {}", expected);
+            if hash::matches(&digest, &expected) {
+                "Same!!"
+            } else {
+                "Different!!"
+            }
+        }
+        None => "Generated!!",
+    };
+
+    let embed = CreateEmbed::default().title(title).description(description);
+
+    ctx.send(privacy.reply(embed)).await?;
+
+    Ok(())
+}
+
+/// Make a hash value with a choice of algorithm and encoding
+#[poise::command(slash_command)]
+#[allow(clippy::too_many_arguments)]
+pub async fn hash(
+    ctx: Context<'_>,
+    #[description = "Value to change, asked in a private form if empty"] input: Option<String>,
+    #[description = "File to hash instead of the input"] file: Option<Attachment>,
+    #[description = "Hash function (default SHA-256)"] algorithm: Option<Algorithm>,
+    #[description = "Output encoding (default hex)"] encoding: Option<Encoding>,
+    #[description = "Digest to compare against, in any encoding"] expected: Option<String>,
+    #[description = "Show the reply to everyone (default false)"] public: Option<bool>,
+    #[description = "Leave the input out of the reply (default false)"] hide_input: Option<bool>,
+) -> Result<(), Error> {
+    let algorithm = algorithm.unwrap_or(Algorithm::Sha256);
+    let encoding = encoding.unwrap_or(Encoding::Hex);
+    let privacy = Privacy::new(public, hide_input);
+
+    send_hash(ctx, input, file, expected, algorithm, encoding, privacy).await
+}
+
+/// Make sha-256 hash value, the same as /hash with SHA-256
 #[poise::command(slash_command)]
 pub async fn sha256(
     ctx: Context<'_>,
-    #[description = "Value to change"] input: String,
+    #[description = "Value to change, asked in a private form if empty"] input: Option<String>,
+    #[description = "File to hash instead of the input"] file: Option<Attachment>,
+    #[description = "Digest to compare against, in any encoding"] expected: Option<String>,
+    #[description = "Show the reply to everyone (default false)"] public: Option<bool>,
+    #[description = "Leave the input out of the reply (default false)"] hide_input: Option<bool>,
+) -> Result<(), Error> {
+    let privacy = Privacy::new(public, hide_input);
+
+    send_hash(
+        ctx,
+        input,
+        file,
+        expected,
+        Algorithm::Sha256,
+        Encoding::Hex,
+        privacy,
+    )
+    .await
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum MacAlgorithm {
+    #[name = "HMAC-SHA256"]
+    Sha256,
+    #[name = "HMAC-SHA512"]
+    Sha512,
+}
+
+/// Make an HMAC of a message with a secret key
+#[poise::command(slash_command)]
+pub async fn hmac(
+    ctx: Context<'_>,
+    #[description = "Secret key, asked in a private form if empty"] key: Option<String>,
+    #[description = "Message, asked in a private form if empty"] input: Option<String>,
+    #[description = "Hash function (default HMAC-SHA256)"] algorithm: Option<MacAlgorithm>,
+    #[description = "Output encoding (default hex)"] encoding: Option<Encoding>,
+    #[description = "Show the reply to everyone (default false)"] public: Option<bool>,
+    #[description = "Leave the input out of the reply (default false)"] hide_input: Option<bool>,
+) -> Result<(), Error> {
+    let algorithm = algorithm.unwrap_or(MacAlgorithm::Sha256);
+    let encoding = encoding.unwrap_or(Encoding::Hex);
+    let privacy = Privacy::new(public, hide_input);
+
+    // 폼은 한 번만 띄울 수 있으므로 키와 메시지를 한 폼에서 받음
+    let (key, input) = match (key, input) {
+        (Some(key), Some(input)) => (key, input),
+        (key, input) => {
+            let Context::Application(ctx) = ctx else {
+                return Ok(());
+            };
+            let defaults = HmacInput {
+                key: key.unwrap_or_default(),
+                input: input.unwrap_or_default(),
+            };
+            let modal = poise::execute_modal(ctx, Some(defaults), Some(MODAL_TIMEOUT)).await?;
+            let Some(modal) = modal else {
+                return Ok(());
+            };
+            (modal.key, modal.input)
+        }
+    };
+
+    let mac = match algorithm {
+        MacAlgorithm::Sha256 => hash::MacAlgorithm::Sha256,
+        MacAlgorithm::Sha512 => hash::MacAlgorithm::Sha512,
+    }
+    .mac(key.as_bytes(), input.as_bytes());
+
+    // 키는 비밀이므로 응답에 보여주지 않음
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(format!(
+            "{}\nalgorithm: {}\nencoding: {}\nmac: {}",
+            privacy.input(&input),
+            algorithm.name(),
+            encoding.name(),
+            encoding.encoding().encode(&mac)
+        ));
+
+    ctx.send(privacy.reply(embed)).await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Kdf {
+    #[name = "PBKDF2-SHA256"]
+    Pbkdf2Sha256,
+    #[name = "PBKDF2-SHA512"]
+    Pbkdf2Sha512,
+    #[name = "scrypt"]
+    Scrypt,
+    #[name = "bcrypt"]
+    Bcrypt,
+}
+
+/// Hash a password with a key derivation function
+///
+/// PBKDF2 and scrypt give PHC strings, bcrypt gives its own `$2b$` format.
+/// Both can be checked with /verify.
+#[poise::command(slash_command)]
+#[allow(clippy::too_many_arguments)]
+pub async fn kdf(
+    ctx: Context<'_>,
+    #[description = "Value to change, asked in a private form if empty"] input: Option<String>,
+    #[description = "Key derivation function (default PBKDF2-SHA256)"] algorithm: Option<Kdf>,
+    #[description = "PBKDF2 rounds (default 600000)"] rounds: Option<u32>,
+    #[description = "scrypt CPU/memory cost as a power of 2 (default 15)"] log_n: Option<u8>,
+    #[description = "scrypt block size (default 8)"] block_size: Option<u32>,
+    #[description = "scrypt parallelism (default 1)"] parallelism: Option<u32>,
+    #[description = "bcrypt cost as a power of 2 (default 12)"] cost: Option<u32>,
+    #[description = "Show the reply to everyone (default false)"] public: Option<bool>,
+    #[description = "Leave the input out of the reply (default false)"] hide_input: Option<bool>,
 ) -> Result<(), Error> {
-    let mut hasher = Sha256::new();
-    hasher.update(&input);
-    let hash = hasher.finalize();
+    let algorithm = algorithm.unwrap_or(Kdf::Pbkdf2Sha256);
+    let rounds = rounds.unwrap_or(600_000);
+    let log_n = log_n.unwrap_or(15);
+    let block_size = block_size.unwrap_or(8);
+    let parallelism = parallelism.unwrap_or(1);
+    let cost = cost.unwrap_or(12);
+    let privacy = Privacy::new(public, hide_input);
+
+    // 봇이 멈추지 않도록 비용에 상한을 둠
+    let error = match algorithm {
+        Kdf::Pbkdf2Sha256 | Kdf::Pbkdf2Sha512 if !(1000..=MAX_PBKDF2_ROUNDS).contains(&rounds) => {
+            Some("The rounds must be between 1000 and 2000000 inclusive")
+        }
+        Kdf::Scrypt if !(10..=MAX_SCRYPT_LOG_N).contains(&log_n) => {
+            Some("The log_n value must be between 10 and 17 inclusive")
+        }
+        Kdf::Scrypt if !(1..=MAX_SCRYPT_BLOCK_SIZE).contains(&block_size) => {
+            Some("The block size must be between 1 and 16 inclusive")
+        }
+        Kdf::Scrypt if !(1..=MAX_SCRYPT_PARALLELISM).contains(&parallelism) => {
+            Some("The parallelism must be between 1 and 4 inclusive")
+        }
+        // 메모리 사용량은 128 × r × 2^log_n 바이트
+        Kdf::Scrypt if (128 * block_size as u64) << log_n > MAX_SCRYPT_MEMORY => {
+            Some("scrypt may use at most 128 MiB, lower log_n or the block size")
+        }
+        Kdf::Bcrypt if !(4..=MAX_BCRYPT_COST).contains(&cost) => {
+            Some("The cost must be between 4 and 16 inclusive")
+        }
+        _ => None,
+    };
+
+    if let Some(error) = error {
+        return send_error(ctx, error.into(), true).await;
+    }
+
+    let Some(input) = read_input(ctx, input).await? else {
+        return Ok(());
+    };
+
+    privacy.defer(ctx).await?;
+
+    let password = input.clone();
+    let (hash, parameters) = task::spawn_blocking(move || -> Result<(String, String), Error> {
+        let salt = SaltString::generate(&mut OsRng);
+        let password = password.as_bytes();
+
+        Ok(match algorithm {
+            Kdf::Pbkdf2Sha256 | Kdf::Pbkdf2Sha512 => {
+                let ident = match algorithm {
+                    Kdf::Pbkdf2Sha256 => pbkdf2::Algorithm::Pbkdf2Sha256,
+                    _ => pbkdf2::Algorithm::Pbkdf2Sha512,
+                }
+                .ident();
+                let params = pbkdf2::Params {
+                    rounds,
+                    ..Default::default()
+                };
+                let hash =
+                    Pbkdf2.hash_password_customized(password, Some(ident), None, params, &salt)?;
+                (hash.to_string(), format!("rounds: {}", rounds))
+            }
+            Kdf::Scrypt => {
+                let params = scrypt::Params::new(log_n, block_size, parallelism, 32)?;
+                let hash = Scrypt.hash_password_customized(password, None, None, params, &salt)?;
+                (
+                    hash.to_string(),
+                    format!(
+                        "log_n: {}\nblock size: {}\nparallelism: {}",
+                        log_n, block_size, parallelism
+                    ),
+                )
+            }
+            Kdf::Bcrypt => (bcrypt::hash(password, cost)?, format!("cost: {}", cost)),
+        })
+    })
+    .await??;
 
     let embed = CreateEmbed::default()
         .title("Generated!!")
-        .description(format!("input: {}\nhash: {:x}", input, hash));
+        .description(format!(
+            "{}\nalgorithm: {}\n{}\nhash: {}",
+            privacy.input(&input),
+            algorithm.name(),
+            parameters,
+            hash
+        ));
 
-    ctx.send(CreateReply::default().embed(embed)).await?;
+    ctx.send(privacy.reply(embed)).await?;
 
     Ok(())
 }
 
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Argon2Variant {
+    #[name = "Argon2id"]
+    Argon2id,
+    #[name = "Argon2i"]
+    Argon2i,
+    #[name = "Argon2d"]
+    Argon2d,
+}
+
 /// Make argon2 hash value
 #[poise::command(slash_command)]
+#[allow(clippy::too_many_arguments)]
 pub async fn argon2(
     ctx: Context<'_>,
-    #[description = "Value to change"] input: String,
+    #[description = "Value to change, asked in a private form if empty"] input: Option<String>,
+    #[description = "Algorithm variant (default Argon2id)"] variant: Option<Argon2Variant>,
+    #[description = "Memory cost in KiB (default 19456)"] memory: Option<u32>,
+    #[description = "Time cost, the number of passes (default 2)"] time: Option<u32>,
+    #[description = "Parallelism, the number of lanes (default 1)"] parallelism: Option<u32>,
+    #[description = "Output length in bytes (default 32)"] length: Option<usize>,
+    #[description = "Show the reply to everyone (default false)"] public: Option<bool>,
+    #[description = "Leave the input out of the reply (default false)"] hide_input: Option<bool>,
 ) -> Result<(), Error> {
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
+    let variant = variant.unwrap_or(Argon2Variant::Argon2id);
+    let memory = memory.unwrap_or(argon2::Params::DEFAULT_M_COST);
+    let time = time.unwrap_or(argon2::Params::DEFAULT_T_COST);
+    let parallelism = parallelism.unwrap_or(argon2::Params::DEFAULT_P_COST);
+    let length = length.unwrap_or(argon2::Params::DEFAULT_OUTPUT_LEN);
+    let privacy = Privacy::new(public, hide_input);
 
-    let hash = argon2
-        .hash_password(input.as_bytes(), &salt)
-        .unwrap()
-        .to_string();
+    // 봇이 멈추지 않도록 비용에 상한을 둠
+    let error = if !(1..=MAX_ARGON2_PARALLELISM).contains(&parallelism) {
+        Some("The parallelism must be between 1 and 8 inclusive")
+    } else if !(8 * parallelism..=MAX_ARGON2_MEMORY).contains(&memory) {
+        Some("The memory cost must be between 8 KiB per lane and 262144 KiB inclusive")
+    } else if !(1..=MAX_ARGON2_TIME).contains(&time) {
+        Some("The time cost must be between 1 and 10 inclusive")
+    } else if !(16..=64).contains(&length) {
+        Some("The output length must be between 16 and 64 bytes inclusive")
+    } else {
+        None
+    };
+
+    if let Some(error) = error {
+        return send_error(ctx, error.into(), true).await;
+    }
+
+    let Some(input) = read_input(ctx, input).await? else {
+        return Ok(());
+    };
+
+    privacy.defer(ctx).await?;
+
+    let password = input.clone();
+    let hash = task::spawn_blocking(move || -> Result<String, argon2::password_hash::Error> {
+        let salt = SaltString::generate(&mut OsRng);
+        let algorithm = match variant {
+            Argon2Variant::Argon2id => argon2::Algorithm::Argon2id,
+            Argon2Variant::Argon2i => argon2::Algorithm::Argon2i,
+            Argon2Variant::Argon2d => argon2::Algorithm::Argon2d,
+        };
+        let params = argon2::Params::new(memory, time, parallelism, Some(length))?;
+        let argon2 = Argon2::new(algorithm, argon2::Version::V0x13, params);
+
+        Ok(argon2
+            .hash_password(password.as_bytes(), &salt)?
+            .to_string())
+    })
+    .await??;
 
     let embed = CreateEmbed::default()
         .title("Generated!!")
-        .description(format!("input: {}\nhash: {}", input, hash));
+        .description(format!(
+        "{}\nvariant: {}\nmemory: {} KiB\ntime: {}\nparallelism: {}\nlength: {} bytes\nhash: {}",
+        privacy.input(&input),
+        variant.name(),
+        memory,
+        time,
+        parallelism,
+        length,
+        hash
+    ));
 
-    ctx.send(CreateReply::default().embed(embed)).await?;
+    ctx.send(privacy.reply(embed)).await?;
 
     Ok(())
 }
 
-/// Verify argon2 hash value
+/// Check a password against a PHC string or a bcrypt hash, the verifier is picked
+/// by the algorithm named in the hash
+///
+/// Returns whether they match and the algorithm and parameters read from the hash.
+fn check_password(password: &str, encoded: &str) -> Result<(bool, String), String> {
+    // bcrypt는 PHC 형식이 아니므로 따로 확인
+    if let Some(rest) = encoded.strip_prefix("$2") {
+        let mut parts = rest.split('$');
+        let (variant, cost) = (
+            parts.next().unwrap_or_default(),
+            parts.next().unwrap_or_default(),
+        );
+        match cost.parse::<u32>() {
+            Ok(cost) if cost <= MAX_BCRYPT_COST => {}
+            _ => {
+                return Err(format!(
+                    "The bcrypt cost must be a number of at most {}",
+                    MAX_BCRYPT_COST
+                ))
+            }
+        }
+        let same = bcrypt::verify(password, encoded).map_err(|e| e.to_string())?;
+        return Ok((
+            same,
+            format!("algorithm: bcrypt ($2{})\ncost: {}", variant, cost),
+        ));
+    }
+
+    let parsed = PasswordHash::new(encoded).map_err(|e| e.to_string())?;
+    let Some(output) = parsed.hash else {
+        return Err("The hash has no output after the salt".into());
+    };
+
+    check_costs(&parsed)?;
+
+    let verifier: &dyn PasswordVerifier = match parsed.algorithm.as_str() {
+        "argon2id" | "argon2i" | "argon2d" => &Argon2::default(),
+        "pbkdf2" | "pbkdf2-sha256" | "pbkdf2-sha512" => &Pbkdf2,
+        "scrypt" => &Scrypt,
+        algorithm => return Err(format!("`{}` is not a supported algorithm", algorithm)),
+    };
+
+    let mut description = format!("algorithm: {}", parsed.algorithm);
+    if let Some(version) = parsed.version {
+        description += &format!("\nversion: {}", version);
+    }
+    for (name, value) in parsed.params.iter() {
+        description += &format!("\n{}: {}", name, value);
+    }
+    description += &format!("\noutput length: {} bytes", output.len());
+
+    // 형식은 맞지만 매개변수가 잘못된 경우는 오류로 알림
+    match verifier.verify_password(password.as_bytes(), &parsed) {
+        Ok(()) => Ok((true, description)),
+        Err(argon2::password_hash::Error::Password) => Ok((false, description)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Reject a PHC string whose costs are above what /argon2 and /kdf allow, missing
+/// parameters fall back to defaults that are within the limits
+fn check_costs(parsed: &PasswordHash) -> Result<(), String> {
+    let param = |name: &str| parsed.params.get_decimal(name);
+
+    let too_costly = match parsed.algorithm.as_str() {
+        "argon2id" | "argon2i" | "argon2d" => {
+            param("m").is_some_and(|m| m > MAX_ARGON2_MEMORY)
+                || param("t").is_some_and(|t| t > MAX_ARGON2_TIME)
+                || param("p").is_some_and(|p| p > MAX_ARGON2_PARALLELISM)
+        }
+        "pbkdf2" | "pbkdf2-sha256" | "pbkdf2-sha512" => {
+            param("i").is_some_and(|i| i > MAX_PBKDF2_ROUNDS)
+        }
+        "scrypt" => {
+            let log_n = param("ln").unwrap_or(scrypt::Params::RECOMMENDED_LOG_N as u32);
+            let block_size = param("r").unwrap_or(scrypt::Params::RECOMMENDED_R);
+            log_n > MAX_SCRYPT_LOG_N as u32
+                || block_size > MAX_SCRYPT_BLOCK_SIZE
+                || param("p").is_some_and(|p| p > MAX_SCRYPT_PARALLELISM)
+                // 메모리 사용량은 128 × r × 2^log_n 바이트
+                || (128 * block_size as u64) << log_n > MAX_SCRYPT_MEMORY
+        }
+        _ => false,
+    };
+
+    if too_costly {
+        return Err(format!(
+            "The hash is too costly to check, the bot allows at most argon2 m={}, t={}, p={}, \
+             pbkdf2 i={} and scrypt ln={}, r={}, p={} within {} MiB",
+            MAX_ARGON2_MEMORY,
+            MAX_ARGON2_TIME,
+            MAX_ARGON2_PARALLELISM,
+            MAX_PBKDF2_ROUNDS,
+            MAX_SCRYPT_LOG_N,
+            MAX_SCRYPT_BLOCK_SIZE,
+            MAX_SCRYPT_PARALLELISM,
+            MAX_SCRYPT_MEMORY / 1024 / 1024
+        ));
+    }
+
+    Ok(())
+}
+
+/// Verify a password hash from /argon2 or /kdf
 #[poise::command(slash_command)]
 pub async fn verify(
     ctx: Context<'_>,
-    #[description = "Verify input"] input: String,
+    #[description = "Verify input, asked in a private form if empty"] input: Option<String>,
     #[description = "Verify hash"] hash: String,
+    #[description = "Show the reply to everyone (default false)"] public: Option<bool>,
+    #[description = "Leave the input out of the reply (default false)"] hide_input: Option<bool>,
 ) -> Result<(), Error> {
-    let parsed_hash = PasswordHash::new(&hash).unwrap();
-    if Argon2::default()
-        .verify_password(input.as_bytes(), &parsed_hash)
-        .is_ok()
-    {
-        let embed = CreateEmbed::default()
-            .title("Same!!")
-            .description(format!("input: {}\nhash: {}", input, hash));
-
-        ctx.send(CreateReply::default().embed(embed)).await?;
-    } else {
-        let embed = CreateEmbed::default()
-            .title("Different!!")
-            .description(format!("input: {}\nhash: {}", input, hash));
+    let privacy = Privacy::new(public, hide_input);
+    let Some(input) = read_input(ctx, input).await? else {
+        return Ok(());
+    };
 
-        ctx.send(CreateReply::default().embed(embed)).await?;
-    }
+    privacy.defer(ctx).await?;
+
+    let (password, encoded) = (input.clone(), hash.trim().to_string());
+    let checked = task::spawn_blocking(move || check_password(&password, &encoded)).await?;
+
+    let (same, parameters) = match checked {
+        Ok(checked) => checked,
+        Err(e) => {
+            return send_error(
+                ctx,
+                format!(
+                    "The hash is not a valid Argon2, scrypt, PBKDF2 or bcrypt hash\n{}",
+                    e
+                ),
+                true,
+            )
+            .await
+        }
+    };
+
+    let embed = CreateEmbed::default()
+        .title(if same { "Same!!" } else { "Different!!" })
+        .description(format!(
+            "{}\nhash: {}\n{}",
+            privacy.input(&input),
+            hash,
+            parameters
+        ));
+
+    ctx.send(privacy.reply(embed)).await?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_hashes() {
+        // 파이썬 hashlib과 bcrypt 크레이트의 테스트에서 만든 값
+        let hashes = [
+            (
+                "password",
+                "$pbkdf2-sha256$i=1000,l=32$c2FsdHNhbHRzYWx0c2FsdA\
+                 $8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            ),
+            (
+                "password",
+                "$pbkdf2-sha512$i=1000,l=64$c2FsdHNhbHRzYWx0c2FsdA\
+                 $715rqIr5dXOVPpBhqqsugl037zT5bWJTWYmZtIcK8hBnisKpwfY7kokvwjDrNHqHhF50Pb7MD6HvkJwiDQw4ww",
+            ),
+            (
+                "password",
+                "$scrypt$ln=10,r=8,p=1$c2FsdHNhbHRzYWx0c2FsdA\
+                 $BVMRKqdiVYikKAaPR1wucsKUKvw4TuPLkdEYtoSHas4",
+            ),
+            (
+                "password",
+                "$2a$04$UuTkLRZZ6QofpDOlMz32MuuxEHA43WOemOYHPz6.SjsVsyO1tDU96",
+            ),
+            (
+                "correctbatteryhorsestapler",
+                "$2b$04$EGdrhbKUv8Oc9vGiXX0HQOxSg445d458Muh7DAHskb6QbtCvdxcie",
+            ),
+        ];
+
+        for (password, hash) in hashes {
+            assert!(check_password(password, hash).unwrap().0, "{}", hash);
+            assert!(!check_password("wrong", hash).unwrap().0, "{}", hash);
+        }
+    }
+
+    #[test]
+    fn generated_hashes() {
+        let salt = SaltString::generate(&mut OsRng);
+        let password = b"hunter2";
+
+        let mut hashes = vec![bcrypt::hash(password, 4).unwrap()];
+        for algorithm in [
+            pbkdf2::Algorithm::Pbkdf2Sha256,
+            pbkdf2::Algorithm::Pbkdf2Sha512,
+        ] {
+            let params = pbkdf2::Params {
+                rounds: 1000,
+                ..Default::default()
+            };
+            let hash = Pbkdf2
+                .hash_password_customized(password, Some(algorithm.ident()), None, params, &salt)
+                .unwrap();
+            hashes.push(hash.to_string());
+        }
+        let params = scrypt::Params::new(10, 8, 1, 32).unwrap();
+        let hash = Scrypt
+            .hash_password_customized(password, None, None, params, &salt)
+            .unwrap();
+        hashes.push(hash.to_string());
+
+        for hash in hashes {
+            let (same, description) = check_password("hunter2", &hash).unwrap();
+            assert!(same, "{}", hash);
+            assert!(description.starts_with("algorithm: "));
+            assert!(!check_password("hunter3", &hash).unwrap().0, "{}", hash);
+        }
+    }
+
+    #[test]
+    fn costly_hashes() {
+        // 상한을 넘는 비용은 계산하기 전에 거부되어야 함
+        for hash in [
+            "$2b$17$EGdrhbKUv8Oc9vGiXX0HQOxSg445d458Muh7DAHskb6QbtCvdxcie",
+            "$2b$xx$EGdrhbKUv8Oc9vGiXX0HQOxSg445d458Muh7DAHskb6QbtCvdxcie",
+            "$argon2id$v=19$m=1048576,t=2,p=1$c2FsdHNhbHRzYWx0c2FsdA\
+             $BVMRKqdiVYikKAaPR1wucsKUKvw4TuPLkdEYtoSHas4",
+            "$argon2id$v=19$m=19456,t=11,p=1$c2FsdHNhbHRzYWx0c2FsdA\
+             $BVMRKqdiVYikKAaPR1wucsKUKvw4TuPLkdEYtoSHas4",
+            "$pbkdf2-sha256$i=3000000,l=32$c2FsdHNhbHRzYWx0c2FsdA\
+             $8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            "$scrypt$ln=20,r=8,p=1$c2FsdHNhbHRzYWx0c2FsdA\
+             $BVMRKqdiVYikKAaPR1wucsKUKvw4TuPLkdEYtoSHas4",
+            "$scrypt$ln=17,r=16,p=1$c2FsdHNhbHRzYWx0c2FsdA\
+             $BVMRKqdiVYikKAaPR1wucsKUKvw4TuPLkdEYtoSHas4",
+            "$scrypt$ln=10,r=8,p=5$c2FsdHNhbHRzYWx0c2FsdA\
+             $BVMRKqdiVYikKAaPR1wucsKUKvw4TuPLkdEYtoSHas4",
+        ] {
+            let error = check_password("password", hash).unwrap_err();
+            assert!(error.contains("cost"), "{}: {}", hash, error);
+        }
+    }
+}
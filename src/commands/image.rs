@@ -1,13 +1,16 @@
+use super::send_error;
 use crate::{Context, Error};
+use arcum::color::{self, Color};
+use arcum::gradient::{Gradient, Space};
+use arcum::lsystem;
+use arcum::seed::Seed;
 use bytes::Bytes;
-use image::{Rgb, RgbImage};
+use image::{Rgb, RgbImage, RgbaImage};
 use ndarray::Array2;
 use num_complex::Complex;
 use poise::serenity_prelude::{CreateAttachment, CreateEmbed};
-use poise::CreateReply;
+use poise::{ChoiceParameter, CreateReply};
 use rand::Rng;
-use rand::SeedableRng;
-use rand_chacha::ChaCha8Rng;
 use std::io::Cursor;
 use tokio::task;
 
@@ -68,13 +71,12 @@ pub async fn ptf(
     #[description = "number of y points"] ny: usize,
 ) -> Result<(), Error> {
     if nx > 3840 || ny > 3840 || nx < 1 || ny < 1 {
-        let embed = CreateEmbed::default()
-            .title("Error!!")
-            .description("The values of nx and ny must be between 1 and 3840 inclusive.");
-
-        ctx.send(CreateReply::default().embed(embed)).await?;
-
-        return Ok(());
+        return send_error(
+            ctx,
+            "The values of nx and ny must be between 1 and 3840 inclusive.".into(),
+            false,
+        )
+        .await;
     }
 
     let embed = CreateEmbed::default()
@@ -133,16 +135,15 @@ pub async fn bwimg(
     ctx: Context<'_>,
     #[description = "the width of the image"] width: usize,
     #[description = "the height of the image"] height: usize,
-    #[description = "the seed for the random generator"] seed: Option<u64>,
+    #[description = "the seed for the random generator, a number or any text"] seed: Option<String>,
 ) -> Result<(), Error> {
     if width > 3840 || height > 3840 || width < 1 || height < 1 {
-        let embed = CreateEmbed::default()
-            .title("Error!!")
-            .description("The width and height values must be between 1 and 3840 inclusive");
-
-        ctx.send(CreateReply::default().embed(embed)).await?;
-
-        return Ok(());
+        return send_error(
+            ctx,
+            "The width and height values must be between 1 and 3840 inclusive".into(),
+            false,
+        )
+        .await;
     }
 
     let embed = CreateEmbed::default()
@@ -151,18 +152,11 @@ pub async fn bwimg(
 
     ctx.send(CreateReply::default().embed(embed)).await?;
 
+    let seed = Seed::from_option(seed.as_deref());
+    let mut rng = seed.rng();
+
     // 백그라운드 스레드에서 이미지 생성
     let img = task::spawn_blocking(move || {
-        let mut rng = match seed {
-            Some(s) => {
-                let mut seed_array = [0u8; 32];
-                let s_bytes = s.to_le_bytes();
-                seed_array[0..8].copy_from_slice(&s_bytes);
-                ChaCha8Rng::from_seed(seed_array)
-            }
-            None => ChaCha8Rng::from_entropy(),
-        };
-
         let mut img = RgbImage::new(width as u32, height as u32);
 
         for x in 0..width {
@@ -187,11 +181,7 @@ pub async fn bwimg(
 
     let attachment = CreateAttachment::bytes(&*buffer, "random_image.png");
 
-    let mut description = format!("width : {}\nheight : {}", width, height);
-
-    if let Some(seed_value) = seed {
-        description.push_str(&format!("\nseed: {}", seed_value));
-    }
+    let description = format!("width : {}\nheight : {}\nseed : {}", width, height, seed);
 
     let embed = CreateEmbed::default()
         .title("Generated!!")
@@ -209,16 +199,15 @@ pub async fn rgbimg(
     ctx: Context<'_>,
     #[description = "the width of the image"] width: usize,
     #[description = "the height of the image"] height: usize,
-    #[description = "the seed for the random generator"] seed: Option<u64>,
+    #[description = "the seed for the random generator, a number or any text"] seed: Option<String>,
 ) -> Result<(), Error> {
     if width > 1000 || height > 1000 || width < 1 || height < 1 {
-        let embed = CreateEmbed::default()
-            .title("Error!!")
-            .description("The width and height values must be between 1 and 1000 inclusive");
-
-        ctx.send(CreateReply::default().embed(embed)).await?;
-
-        return Ok(());
+        return send_error(
+            ctx,
+            "The width and height values must be between 1 and 1000 inclusive".into(),
+            false,
+        )
+        .await;
     }
 
     let embed = CreateEmbed::default()
@@ -227,18 +216,11 @@ pub async fn rgbimg(
 
     ctx.send(CreateReply::default().embed(embed)).await?;
 
+    let seed = Seed::from_option(seed.as_deref());
+    let mut rng = seed.rng();
+
     // 백그라운드 스레드에서 이미지 생성
     let img = task::spawn_blocking(move || {
-        let mut rng = match seed {
-            Some(s) => {
-                let mut seed_array = [0u8; 32];
-                let s_bytes = s.to_le_bytes();
-                seed_array[0..8].copy_from_slice(&s_bytes);
-                ChaCha8Rng::from_seed(seed_array)
-            }
-            None => ChaCha8Rng::from_entropy(),
-        };
-
         let mut img = RgbImage::new(width as u32, height as u32);
 
         for x in 0..width {
@@ -263,11 +245,7 @@ pub async fn rgbimg(
 
     let attachment = CreateAttachment::bytes(&*buffer, "random_rgb_image.png");
 
-    let mut description = format!("width : {}\nheight : {}", width, height);
-
-    if let Some(seed_value) = seed {
-        description.push_str(&format!("\nseed: {}", seed_value));
-    }
+    let description = format!("width : {}\nheight : {}\nseed : {}", width, height, seed);
 
     let embed = CreateEmbed::default()
         .title("Generated!!")
@@ -279,182 +257,364 @@ pub async fn rgbimg(
     Ok(())
 }
 
-/// Display the color of the hex code
+/// Display a color in every format
 #[poise::command(slash_command)]
 pub async fn hexcode(
     ctx: Context<'_>,
-    #[description = "Enter the hex code"] hex_code: String,
+    #[description = "Enter the color (hex code, rgb(), hsl(), hsv(), cmyk() or a name)"]
+    hex_code: String,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let example = "\nExample : FFBF98\nCase does not matter";
+    let color = match Color::parse(&hex_code) {
+        Ok(color) => color,
+        Err(e) => {
+            return send_error(
+                ctx,
+                format!("{}\nAccepted formats : {}", e, color::FORMATS),
+                false,
+            )
+            .await;
+        }
+    };
 
-    if hex_code.len() != 6 {
-        let embed = CreateEmbed::default().title("Error!!").description(format!(
-            "The hex code must be exactly 6 characters{example}"
-        ));
+    let img = RgbaImage::from_pixel(100, 100, color.into());
 
-        ctx.send(CreateReply::default().embed(embed)).await?;
+    let mut buffer = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)
+        .expect("Failed to write image");
 
-        return Ok(());
-    }
+    let attachment = CreateAttachment::bytes(Bytes::from(buffer), "color.png");
 
-    let hex_code = hex_code.to_lowercase();
+    let (name, named) = color.nearest_name();
+    let nearest = if named == Color::rgb(color.r, color.g, color.b) {
+        name.to_string()
+    } else {
+        format!("{} ({})", name, named)
+    };
 
-    let chunks: Vec<String> = hex_code
-        .chars()
-        .collect::<Vec<char>>()
-        .chunks(2)
-        .map(|chunk| chunk.iter().collect::<String>())
-        .collect();
-    let hex1 = &chunks[0];
-    let hex2 = &chunks[1];
-    let hex3 = &chunks[2];
-
-    let parse_hex = |hex: &str| u8::from_str_radix(hex, 16);
-    match (parse_hex(hex1), parse_hex(hex2), parse_hex(hex3)) {
-        (Ok(val1), Ok(val2), Ok(val3)) => {
-            let img = RgbImage::new(100, 100);
-            let mut img = img.clone();
-
-            for pixel in img.pixels_mut() {
-                *pixel = Rgb([val1, val2, val3]);
-            }
+    let embed = CreateEmbed::default()
+        .title("Generated Color!!")
+        .description(format!(
+            "hex : {}\nrgb : {}\nhsl : {}\nhsv : {}\ncmyk : {}\nnearest name : {}",
+            color,
+            color.to_rgb_string(),
+            color.to_hsl_string(),
+            color.to_hsv_string(),
+            color.to_cmyk_string(),
+            nearest
+        ));
 
-            let mut buffer = Vec::new();
-            img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)
-                .expect("Failed to write image");
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
 
-            let attachment = CreateAttachment::bytes(Bytes::from(buffer), "color.png");
+    Ok(())
+}
 
-            let embed = CreateEmbed::default()
-                .title("Generated Color!!")
-                .description(format!("{}", hex_code.to_lowercase()));
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum GradientKind {
+    Linear,
+    Radial,
+    Conic,
+}
 
-            ctx.send(CreateReply::default().embed(embed).attachment(attachment))
-                .await?;
-        }
-        _ => {
-            let embed = CreateEmbed::default()
-                .title("Error!!")
-                .description(format!("The hex code must be hexadecimal{example}"));
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Interpolation {
+    #[name = "OKLab"]
+    Oklab,
+    #[name = "linear RGB"]
+    LinearRgb,
+    #[name = "HSL"]
+    Hsl,
+    #[name = "sRGB"]
+    Srgb,
+}
 
-            ctx.send(CreateReply::default().embed(embed)).await?;
+impl Interpolation {
+    fn space(self) -> Space {
+        match self {
+            Interpolation::Oklab => Space::Oklab,
+            Interpolation::LinearRgb => Space::LinearRgb,
+            Interpolation::Hsl => Space::Hsl,
+            Interpolation::Srgb => Space::Srgb,
         }
     }
+}
 
-    Ok(())
+/// Position on the gradient, from 0 to 1, of a point relative to the center of the image
+///
+/// Angles follow CSS, 0 degrees points up and they grow clockwise.
+fn gradient_position(
+    kind: GradientKind,
+    angle: f64,
+    dx: f64,
+    dy: f64,
+    width: f64,
+    height: f64,
+) -> f64 {
+    match kind {
+        GradientKind::Linear => {
+            let (sin, cos) = angle.to_radians().sin_cos();
+            // 그라디언트 선은 모서리까지 닿는 길이
+            let length = (width * sin).abs() + (height * cos).abs();
+            (dx * sin - dy * cos) / length + 0.5
+        }
+        GradientKind::Radial => {
+            // 가장 먼 모서리까지 닿는 타원
+            let (rx, ry) = (dx / (width / 2.0), dy / (height / 2.0));
+            (rx * rx + ry * ry).sqrt() / 2f64.sqrt()
+        }
+        GradientKind::Conic => (dx.atan2(-dy).to_degrees() - angle).rem_euclid(360.0) / 360.0,
+    }
 }
 
-/// Display the gradient colors
+/// Display a gradient through any number of colors
 #[poise::command(slash_command)]
+#[allow(clippy::too_many_arguments)]
 pub async fn gradient(
     ctx: Context<'_>,
-    #[description = "Enter the left top hex code"] left_top_hex_code: String,
-    #[description = "Enter the left bottom hex code"] left_bottom_hex_code: String,
-    #[description = "Enter the right top hex code"] right_top_hex_code: String,
-    #[description = "Enter the right bottom hex code"] right_bottom_hex_code: String,
+    #[description = "the colors separated by commas, optionally with positions like `red, #00f 30%, white`"]
+    colors: String,
+    #[description = "the shape of the gradient (default linear)"] kind: Option<GradientKind>,
+    #[description = "the angle in degrees, 0 is up, 90 is right (default 90)"] angle: Option<f64>,
+    #[description = "the space to blend in (default OKLab)"] interpolation: Option<Interpolation>,
+    #[description = "the width of the image (default 256)"] width: Option<u32>,
+    #[description = "the height of the image (default 256)"] height: Option<u32>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
-    let example = "\nExample : FFBF98\nCase does not matter";
-
-    if left_top_hex_code.len() != 6
-        || left_bottom_hex_code.len() != 6
-        || right_top_hex_code.len() != 6
-        || right_bottom_hex_code.len() != 6
-    {
-        let embed = CreateEmbed::default().title("Error!!").description(format!(
-            "The hex code must be exactly 6 characters{example}"
-        ));
+    let kind = kind.unwrap_or(GradientKind::Linear);
+    let angle = angle.unwrap_or(90.0);
+    let interpolation = interpolation.unwrap_or(Interpolation::Oklab);
+    let width = width.unwrap_or(256);
+    let height = height.unwrap_or(256);
+
+    let gradient = match colors.parse::<Gradient>() {
+        Ok(gradient) => gradient,
+        Err(e) => {
+            return send_error(
+                ctx,
+                format!("{}\nAccepted formats : {}", e, color::FORMATS),
+                false,
+            )
+            .await;
+        }
+    };
 
-        ctx.send(CreateReply::default().embed(embed)).await?;
+    let error = if !(1..=2048).contains(&width) || !(1..=2048).contains(&height) {
+        Some("The width and height values must be between 1 and 2048 inclusive")
+    } else if !angle.is_finite() {
+        Some("The angle must be a number")
+    } else {
+        None
+    };
 
-        return Ok(());
+    if let Some(error) = error {
+        return send_error(ctx, error.into(), false).await;
     }
 
-    let parse_hex = |hex_code: &str| {
-        let chunks: Vec<String> = hex_code
-            .to_lowercase()
-            .chars()
-            .collect::<Vec<char>>()
-            .chunks(2)
-            .map(|chunk| chunk.iter().collect::<String>())
-            .collect();
-
-        return if let Ok(a) = u8::from_str_radix(&chunks[0], 16) {
-            if let Ok(b) = u8::from_str_radix(&chunks[1], 16) {
-                if let Ok(c) = u8::from_str_radix(&chunks[2], 16) {
-                    Some([a, b, c])
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-    };
+    let stops: Vec<String> = gradient
+        .stops()
+        .iter()
+        .map(|(position, color)| format!("{} {}%", color, (position * 1000.0).round() / 10.0))
+        .collect();
 
-    match (
-        parse_hex(&left_top_hex_code),
-        parse_hex(&left_bottom_hex_code),
-        parse_hex(&right_top_hex_code),
-        parse_hex(&right_bottom_hex_code),
-    ) {
-        (Some(lt), Some(lb), Some(rt), Some(rb)) => {
-            fn interpolate(c1: Rgb<u8>, c2: Rgb<u8>, t: f32) -> Rgb<u8> {
-                Rgb([
-                    ((1.0 - t) * c1[0] as f32 + t * c2[0] as f32) as u8,
-                    ((1.0 - t) * c1[1] as f32 + t * c2[1] as f32) as u8,
-                    ((1.0 - t) * c1[2] as f32 + t * c2[2] as f32) as u8,
-                ])
-            }
+    // 백그라운드 스레드에서 이미지 생성
+    let img = task::spawn_blocking(move || {
+        let space = interpolation.space();
+        let (w, h) = (width as f64, height as f64);
+
+        RgbaImage::from_fn(width, height, |x, y| {
+            // 픽셀의 중심을 기준으로 계산
+            let dx = x as f64 + 0.5 - w / 2.0;
+            let dy = y as f64 + 0.5 - h / 2.0;
+            let t = gradient_position(kind, angle, dx, dy, w, h);
+            gradient.sample(t, space).into()
+        })
+    })
+    .await
+    .unwrap();
 
-            let mut img = RgbImage::new(100, 100);
-            let lt = Rgb(lt);
-            let lb = Rgb(lb);
-            let rt = Rgb(rt);
-            let rb = Rgb(rb);
+    let mut buffer = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
 
-            for y in 0..100 {
-                let left = interpolate(lt, lb, y as f32 / 99.0);
-                let right = interpolate(rt, rb, y as f32 / 99.0);
+    let attachment = CreateAttachment::bytes(&*buffer, "gradient.png");
 
-                for x in 0..100 {
-                    let color = interpolate(left, right, x as f32 / 99.0);
-                    img.put_pixel(x, y, color);
-                }
-            }
+    let embed = CreateEmbed::default()
+        .title("Generated Gradient!!")
+        .description(format!(
+            "stops : {}\nkind : {}\nangle : {}\ninterpolation : {}\nwidth : {}\nheight : {}",
+            stops.join(", "),
+            kind.name(),
+            angle,
+            interpolation.name(),
+            width,
+            height
+        ));
 
-            let mut buffer = Vec::new();
-            img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)
-                .expect("Failed to write image");
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
+
+    Ok(())
+}
 
-            let attachment = CreateAttachment::bytes(Bytes::from(buffer), "gradient.png");
+/// Longest string an L-system may grow to before it is drawn
+const MAX_LSYSTEM_LENGTH: usize = 500_000;
+
+/// Most lines drawn by /lsystem
+const MAX_LSYSTEM_SEGMENTS: usize = 200_000;
+
+/// Most pixels /lsystem looks at while drawing, counted again for every line near them
+const MAX_LSYSTEM_AREA: u64 = 200_000_000;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum LSystemPreset {
+    #[name = "Koch snowflake"]
+    KochSnowflake,
+    #[name = "Sierpinski triangle"]
+    SierpinskiTriangle,
+    #[name = "Sierpinski arrowhead"]
+    SierpinskiArrowhead,
+    #[name = "dragon curve"]
+    DragonCurve,
+    #[name = "Hilbert curve"]
+    HilbertCurve,
+    #[name = "fractal plant"]
+    FractalPlant,
+}
 
-            let embed = CreateEmbed::default()
-                .title("Generated Gradient!!")
-                .description(format!(
-                    "left top : {}\nleft bottom : {}\nright top : {}\nright bottom : {}",
-                    left_top_hex_code,
-                    left_bottom_hex_code,
-                    right_top_hex_code,
-                    right_bottom_hex_code
-                ));
+impl LSystemPreset {
+    fn preset(self) -> lsystem::Preset {
+        match self {
+            LSystemPreset::KochSnowflake => lsystem::KOCH_SNOWFLAKE,
+            LSystemPreset::SierpinskiTriangle => lsystem::SIERPINSKI_TRIANGLE,
+            LSystemPreset::SierpinskiArrowhead => lsystem::SIERPINSKI_ARROWHEAD,
+            LSystemPreset::DragonCurve => lsystem::DRAGON_CURVE,
+            LSystemPreset::HilbertCurve => lsystem::HILBERT_CURVE,
+            LSystemPreset::FractalPlant => lsystem::FRACTAL_PLANT,
+        }
+    }
+}
 
-            ctx.send(CreateReply::default().embed(embed).attachment(attachment))
-                .await?;
+/// Draw an L-system with turtle graphics
+#[poise::command(slash_command)]
+#[allow(clippy::too_many_arguments)]
+pub async fn lsystem(
+    ctx: Context<'_>,
+    #[description = "a well-known L-system to start from"] preset: Option<LSystemPreset>,
+    #[description = "the starting string, F and G draw, f moves, + and - turn, [ and ] save and restore"]
+    axiom: Option<String>,
+    #[description = "the rules like `F=F+F--F+F, X=F[+X]F`"] rules: Option<String>,
+    #[description = "the angle of a turn in degrees"] angle: Option<f64>,
+    #[description = "the number of times the rules are applied"] iterations: Option<u32>,
+    #[description = "the width and height of the image (default 1024)"] size: Option<u32>,
+    #[description = "the thickness of the lines in pixels (default 1.5)"] thickness: Option<f64>,
+    #[description = "the color of the lines (default white)"] color: Option<String>,
+    #[description = "the color of the background (default black)"] background: Option<String>,
+) -> Result<(), Error> {
+    let preset = preset.map(LSystemPreset::preset);
+    let axiom = axiom.or_else(|| preset.map(|preset| preset.axiom.to_string()));
+    let rules = rules.or_else(|| preset.map(|preset| preset.rules.to_string()));
+    let angle = angle.or(preset.map(|preset| preset.angle)).unwrap_or(90.0);
+    let heading = preset.map_or(0.0, |preset| preset.heading);
+    let iterations = iterations
+        .or(preset.map(|preset| preset.iterations))
+        .unwrap_or(4);
+    let size = size.unwrap_or(1024);
+    let thickness = thickness.unwrap_or(1.5);
+
+    let colors = (
+        color
+            .as_deref()
+            .map_or(Ok(Color::rgb(255, 255, 255)), Color::parse),
+        background
+            .as_deref()
+            .map_or(Ok(Color::rgb(0, 0, 0)), Color::parse),
+    );
+    let (foreground, background) = match colors {
+        (Ok(foreground), Ok(background)) => (foreground, background),
+        (Err(e), _) | (_, Err(e)) => {
+            return send_error(
+                ctx,
+                format!("{}\nAccepted formats : {}", e, color::FORMATS),
+                false,
+            )
+            .await;
         }
-        _ => {
-            let embed = CreateEmbed::default()
-                .title("Error!!")
-                .description(format!("The hex code must be hexadecimal{example}"));
+    };
 
-            ctx.send(CreateReply::default().embed(embed)).await?;
+    let error = match &axiom {
+        None => Some("Choose a preset or give an axiom"),
+        Some(axiom) if axiom.chars().count() > 1000 => {
+            Some("The axiom must be at most 1000 symbols long")
+        }
+        _ if iterations > 20 => Some("The number of iterations must be at most 20"),
+        _ if !(64..=2048).contains(&size) => Some("The size must be between 64 and 2048 inclusive"),
+        _ if !(0.5..=16.0).contains(&thickness) => {
+            Some("The thickness must be between 0.5 and 16 pixels inclusive")
         }
+        _ if !angle.is_finite() => Some("The angle must be a number"),
+        _ => None,
+    };
+
+    if let Some(error) = error {
+        return send_error(ctx, error.into(), false).await;
     }
 
+    let axiom = axiom.unwrap_or_default();
+    let rules = rules.unwrap_or_default();
+
+    ctx.defer().await?;
+
+    // 백그라운드 스레드에서 규칙을 펼치고 거북이가 지나간 경로를 따라 선을 그림
+    let (rules_shown, axiom_shown) = (rules.clone(), axiom.clone());
+    let drawn = task::spawn_blocking(move || -> Result<(RgbaImage, usize), String> {
+        let parsed = lsystem::parse_rules(&rules).map_err(|e| e.to_string())?;
+        let commands = lsystem::expand(&axiom, &parsed, iterations, MAX_LSYSTEM_LENGTH)
+            .map_err(|e| e.to_string())?;
+
+        let lines = lsystem::trace(&commands, angle, heading);
+        if lines.len() > MAX_LSYSTEM_SEGMENTS {
+            return Err(format!(
+                "The drawing would have more than {} lines, use fewer iterations",
+                MAX_LSYSTEM_SEGMENTS
+            ));
+        }
+
+        let img = lsystem::render(
+            &lines,
+            size,
+            size,
+            thickness,
+            foreground,
+            background,
+            MAX_LSYSTEM_AREA,
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok((img, lines.len()))
+    })
+    .await?;
+
+    let (img, segments) = match drawn {
+        Ok(drawn) => drawn,
+        Err(e) => return send_error(ctx, e, false).await,
+    };
+
+    let mut buffer = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
+
+    let attachment = CreateAttachment::bytes(&*buffer, "lsystem.png");
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(format!(
+            "axiom : `{}`\nrules : `{}`\nangle : {}\niterations : {}\nsegments : {}",
+            axiom_shown, rules_shown, angle, iterations, segments
+        ));
+
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
+
     Ok(())
 }
@@ -0,0 +1,233 @@
+use super::send_error;
+use crate::{Context, Error};
+use arcum::life::{Board, LifeError, Pattern, Rule};
+use arcum::seed::Seed;
+use gif::{DisposalMethod, Encoder, Frame, Repeat};
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed};
+use poise::{ChoiceParameter, CreateReply};
+use tokio::task;
+
+/// Largest width or height of a rendered animation
+const MAX_IMAGE_SIZE: usize = 512;
+/// Largest GIF that is sent, well within Discord's upload limit
+const MAX_OUTPUT_SIZE: usize = 8 * 1024 * 1024;
+/// Extra time the last generation stays on screen before the animation loops
+const FINAL_PAUSE: u16 = 100;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Edges {
+    #[name = "wrap around"]
+    Wrap,
+    #[name = "bounded"]
+    Bounded,
+}
+
+/// A GIF frame covering only the cells that changed since the previous generation
+fn changed_frame(
+    previous: Option<&[bool]>,
+    cells: &[bool],
+    width: usize,
+    cell_size: usize,
+) -> Option<Frame<'static>> {
+    let height = cells.len() / width;
+    let (mut left, mut top, mut right, mut bottom) = (width, height, 0, 0);
+    for (index, &alive) in cells.iter().enumerate() {
+        if previous.is_some_and(|previous| previous[index] == alive) {
+            continue;
+        }
+        let (x, y) = (index % width, index / width);
+        left = left.min(x);
+        right = right.max(x + 1);
+        top = top.min(y);
+        bottom = bottom.max(y + 1);
+    }
+
+    if left >= right {
+        return None;
+    }
+
+    let (frame_width, frame_height) = ((right - left) * cell_size, (bottom - top) * cell_size);
+    let mut pixels = Vec::with_capacity(frame_width * frame_height);
+    for y in 0..frame_height {
+        let row = (top + y / cell_size) * width;
+        for x in 0..frame_width {
+            pixels.push(cells[row + left + x / cell_size] as u8);
+        }
+    }
+
+    Some(Frame {
+        left: (left * cell_size) as u16,
+        top: (top * cell_size) as u16,
+        width: frame_width as u16,
+        height: frame_height as u16,
+        buffer: pixels.into(),
+        dispose: DisposalMethod::Keep,
+        ..Frame::default()
+    })
+}
+
+/// Run the board for some generations and encode every one as a frame
+///
+/// Generations that change nothing only lengthen the previous frame.
+fn animate(
+    board: &mut Board,
+    generations: u32,
+    cell_size: usize,
+    delay: u16,
+) -> Result<Vec<u8>, gif::EncodingError> {
+    let (width, height) = (board.width(), board.height());
+    let mut buffer = Vec::new();
+    {
+        // 죽은 칸은 검은색, 살아 있는 칸은 흰색
+        let mut encoder = Encoder::new(
+            &mut buffer,
+            (width * cell_size) as u16,
+            (height * cell_size) as u16,
+            &[0, 0, 0, 255, 255, 255],
+        )?;
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        let mut previous: Option<Vec<bool>> = None;
+        let mut pending: Option<Frame> = None;
+        for generation in 0..=generations {
+            if generation > 0 {
+                board.step();
+            }
+
+            match changed_frame(previous.as_deref(), board.cells(), width, cell_size) {
+                Some(frame) => {
+                    if let Some(pending) = pending.replace(Frame { delay, ..frame }) {
+                        encoder.write_frame(&pending)?;
+                    }
+                    previous = Some(board.cells().to_vec());
+                }
+                None => {
+                    if let Some(pending) = &mut pending {
+                        pending.delay = pending.delay.saturating_add(delay);
+                    }
+                }
+            }
+        }
+
+        if let Some(mut pending) = pending {
+            pending.delay = pending.delay.saturating_add(FINAL_PAUSE);
+            encoder.write_frame(&pending)?;
+        }
+    }
+
+    Ok(buffer)
+}
+
+/// The rule and the starting pattern, the rule in the pattern's header is used
+/// only when no rule was given
+fn parse_start(
+    rule: Option<&str>,
+    pattern: Option<&str>,
+) -> Result<(Rule, Option<Pattern>), LifeError> {
+    let pattern = pattern.map(str::parse::<Pattern>).transpose()?;
+    let rule = match (rule, pattern.as_ref().and_then(|pattern| pattern.rule)) {
+        (Some(rule), _) => rule.parse()?,
+        (None, Some(rule)) => rule,
+        (None, None) => Rule::LIFE,
+    };
+
+    Ok((rule, pattern))
+}
+
+/// Run Conway's Game of Life or another cellular automaton as an animated GIF
+#[poise::command(slash_command)]
+#[allow(clippy::too_many_arguments)]
+pub async fn life(
+    ctx: Context<'_>,
+    #[description = "a rule like B36/S23, highlife or rule 30 (default life)"] rule: Option<String>,
+    #[description = "the starting pattern in RLE (random if empty)"] pattern: Option<String>,
+    #[description = "the number of generations (default 100)"] generations: Option<u32>,
+    #[description = "the width of the board in cells (default 64)"] width: Option<usize>,
+    #[description = "the height of the board in cells (default 64)"] height: Option<usize>,
+    #[description = "what happens at the edges (default wrap around)"] edges: Option<Edges>,
+    #[description = "the percent alive at a random start (default 50)"] density: Option<u32>,
+    #[description = "the seed for the random generator, a number or any text"] seed: Option<String>,
+    #[description = "the milliseconds between generations (default 100)"] delay: Option<u32>,
+) -> Result<(), Error> {
+    let generations = generations.unwrap_or(100);
+    let width = width.unwrap_or(64);
+    let height = height.unwrap_or(64);
+    let edges = edges.unwrap_or(Edges::Wrap);
+    let density = density.unwrap_or(50);
+    let delay = delay.unwrap_or(100);
+
+    let error = if !(1..=500).contains(&generations) {
+        Some("The number of generations must be between 1 and 500 inclusive")
+    } else if !(8..=256).contains(&width) || !(8..=256).contains(&height) {
+        Some("The width and height values must be between 8 and 256 inclusive")
+    } else if !(1..=99).contains(&density) {
+        Some("The density must be between 1 and 99 inclusive")
+    } else if !(20..=2000).contains(&delay) {
+        Some("The delay must be between 20 and 2000 milliseconds inclusive")
+    } else {
+        None
+    };
+
+    let (rule, pattern) = match (error, parse_start(rule.as_deref(), pattern.as_deref())) {
+        (None, Ok(parsed)) => parsed,
+        (Some(error), _) => return send_error(ctx, error.into(), false).await,
+        (None, Err(e)) => return send_error(ctx, e.to_string(), false).await,
+    };
+
+    let mut board = Board::new(width, height, matches!(edges, Edges::Wrap), rule);
+    let start = match &pattern {
+        Some(pattern) => {
+            if let Err(e) = board.place(pattern) {
+                return send_error(ctx, e.to_string(), false).await;
+            }
+            format!("pattern ({}×{})", pattern.width, pattern.height)
+        }
+        None => {
+            let seed = Seed::from_option(seed.as_deref());
+            board.randomize(&mut seed.rng(), density as f64 / 100.0);
+            format!("random, {}% alive\nseed : {}", density, seed)
+        }
+    };
+
+    ctx.defer().await?;
+
+    let cell_size = (MAX_IMAGE_SIZE / width.max(height)).clamp(1, 16);
+
+    // 백그라운드 스레드에서 세대를 진행하며 GIF 인코딩
+    let (buffer, population) = task::spawn_blocking(move || {
+        let buffer = animate(&mut board, generations, cell_size, (delay / 10) as u16);
+        buffer.map(|buffer| (buffer, board.population()))
+    })
+    .await??;
+
+    if buffer.len() > MAX_OUTPUT_SIZE {
+        return send_error(
+            ctx,
+            "The animation is larger than 8 MiB, use fewer generations or a smaller board".into(),
+            false,
+        )
+        .await;
+    }
+
+    let attachment = CreateAttachment::bytes(&*buffer, "life.gif");
+
+    let description = format!(
+        "rule : {}\nboard : {}×{}\nedges : {}\ngenerations : {}\nstart : {}\nfinal population : {}",
+        rule,
+        width,
+        height,
+        edges.name(),
+        generations,
+        start,
+        population
+    );
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(description);
+
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
+
+    Ok(())
+}
@@ -4,11 +4,85 @@ pub use word_chain::*;
 pub mod image;
 pub use image::*;
 
+pub mod noise;
+pub use noise::*;
+
+pub mod life;
+pub use life::*;
+
+pub mod palette;
+pub use palette::*;
+
+pub mod contrast;
+pub use contrast::*;
+
+pub mod qr;
+pub use qr::*;
+
 pub mod hash;
 pub use hash::*;
 
+pub mod encode;
+pub use encode::*;
+
+pub mod password;
+pub use password::*;
+
+pub mod otp;
+pub use otp::*;
+
+pub mod crypto;
+pub use crypto::*;
+
 pub mod owner;
 pub use owner::*;
 
 pub mod other;
 pub use other::*;
+
+pub mod animation;
+pub use animation::*;
+
+use crate::{Context, Error};
+use poise::serenity_prelude::CreateEmbed;
+use poise::CreateReply;
+
+/// Longest embed description sent, under Discord's limit of 4096 characters
+pub(crate) const MAX_DESCRIPTION: usize = 4000;
+
+/// Characters [`code_block`] adds around the text, the fences and the cut mark
+pub(crate) const CODE_BLOCK_OVERHEAD: usize = 9;
+
+/// Text in a code block, with at most `limit` characters between the fences
+///
+/// Backticks are escaped before cutting, so the escaping counts toward the limit.
+pub(crate) fn code_block(text: &str, limit: usize) -> String {
+    // 코드 블록이 중간에 끝나지 않도록 백틱 사이에 폭 없는 공백을 넣음
+    let mut escaped = text.to_string();
+    while escaped.contains("```") {
+        escaped = escaped.replace("```", "`\u{200b}`\u{200b}`");
+    }
+
+    let mut shown: String = escaped.chars().take(limit).collect();
+    if shown.len() < escaped.len() {
+        shown.push('…');
+    }
+
+    format!("```\n{}\n```", shown)
+}
+
+/// Reply with an error embed, shown only to the user when `ephemeral`
+pub(crate) async fn send_error(
+    ctx: Context<'_>,
+    description: String,
+    ephemeral: bool,
+) -> Result<(), Error> {
+    let embed = CreateEmbed::default()
+        .title("Error!!")
+        .description(description);
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(ephemeral))
+        .await?;
+
+    Ok(())
+}
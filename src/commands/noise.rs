@@ -0,0 +1,341 @@
+use super::send_error;
+use crate::{Context, Error};
+use arcum::seed::Seed;
+use image::{Rgb, RgbImage};
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed};
+use poise::{ChoiceParameter, CreateReply};
+use rand::Rng;
+use std::f64::consts::PI;
+use std::io::Cursor;
+use tokio::task;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum NoiseKind {
+    Perlin,
+    Simplex,
+    Worley,
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum NoisePalette {
+    Grayscale,
+    Terrain,
+    Fire,
+    Ocean,
+    Viridis,
+}
+
+impl NoisePalette {
+    /// Color stops from the lowest value to the highest one
+    fn stops(self) -> &'static [(f64, [u8; 3])] {
+        match self {
+            NoisePalette::Grayscale => &[(0.0, [0, 0, 0]), (1.0, [255, 255, 255])],
+            NoisePalette::Terrain => &[
+                (0.0, [11, 33, 89]),
+                (0.35, [32, 92, 170]),
+                (0.45, [230, 214, 150]),
+                (0.5, [84, 160, 64]),
+                (0.7, [34, 100, 40]),
+                (0.85, [120, 110, 100]),
+                (1.0, [250, 250, 250]),
+            ],
+            NoisePalette::Fire => &[
+                (0.0, [0, 0, 0]),
+                (0.3, [128, 0, 0]),
+                (0.6, [255, 120, 0]),
+                (0.85, [255, 230, 50]),
+                (1.0, [255, 255, 255]),
+            ],
+            NoisePalette::Ocean => &[
+                (0.0, [0, 10, 40]),
+                (0.4, [0, 60, 140]),
+                (0.75, [0, 170, 200]),
+                (1.0, [220, 250, 255]),
+            ],
+            NoisePalette::Viridis => &[
+                (0.0, [68, 1, 84]),
+                (0.25, [59, 82, 139]),
+                (0.5, [33, 145, 140]),
+                (0.75, [94, 201, 98]),
+                (1.0, [253, 231, 37]),
+            ],
+        }
+    }
+
+    fn color(self, t: f64) -> Rgb<u8> {
+        let stops = self.stops();
+        let t = t.clamp(0.0, 1.0);
+        let upper = stops
+            .iter()
+            .position(|&(position, _)| position >= t)
+            .unwrap_or(stops.len() - 1)
+            .max(1);
+        let (p0, c0) = stops[upper - 1];
+        let (p1, c1) = stops[upper];
+        let f = if p1 > p0 { (t - p0) / (p1 - p0) } else { 0.0 };
+
+        Rgb([0, 1, 2].map(|i| (c0[i] as f64 + (c1[i] as f64 - c0[i] as f64) * f).round() as u8))
+    }
+}
+
+/// Fractal Brownian motion settings
+#[derive(Clone, Copy)]
+struct Fractal {
+    octaves: u32,
+    lacunarity: f64,
+    gain: f64,
+}
+
+/// Coherent noise built on a hashed integer lattice, so no permutation table limits the period
+struct Noise {
+    kind: NoiseKind,
+    seed: u64,
+}
+
+impl Noise {
+    fn hash(&self, x: i64, y: i64) -> u64 {
+        // splitmix64로 격자 좌표와 시드를 섞음
+        let mut h = self.seed
+            ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        h = (h ^ (h >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        h = (h ^ (h >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        h ^ (h >> 31)
+    }
+
+    /// Lattice coordinates wrapped to the period, if any
+    fn wrap(x: i64, y: i64, period: Option<(i64, i64)>) -> (i64, i64) {
+        match period {
+            Some((px, py)) => (x.rem_euclid(px), y.rem_euclid(py)),
+            None => (x, y),
+        }
+    }
+
+    fn gradient(&self, x: i64, y: i64, period: Option<(i64, i64)>) -> (f64, f64) {
+        let (x, y) = Self::wrap(x, y, period);
+        let angle = (self.hash(x, y) >> 11) as f64 / (1u64 << 53) as f64 * 2.0 * PI;
+        (angle.cos(), angle.sin())
+    }
+
+    /// Value in about [-1, 1]
+    fn sample(&self, x: f64, y: f64, period: Option<(i64, i64)>) -> f64 {
+        match self.kind {
+            NoiseKind::Perlin => self.perlin(x, y, period),
+            NoiseKind::Simplex => self.simplex(x, y),
+            NoiseKind::Worley => self.worley(x, y, period),
+        }
+    }
+
+    fn perlin(&self, x: f64, y: f64, period: Option<(i64, i64)>) -> f64 {
+        let fade = |t: f64| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+
+        let x0 = x.floor() as i64;
+        let y0 = y.floor() as i64;
+        let fx = x - x0 as f64;
+        let fy = y - y0 as f64;
+
+        let corner = |cx: i64, cy: i64| {
+            let (gx, gy) = self.gradient(x0 + cx, y0 + cy, period);
+            gx * (fx - cx as f64) + gy * (fy - cy as f64)
+        };
+
+        let u = fade(fx);
+        let v = fade(fy);
+        let top = corner(0, 0) + u * (corner(1, 0) - corner(0, 0));
+        let bottom = corner(0, 1) + u * (corner(1, 1) - corner(0, 1));
+
+        // 2차원 Perlin 노이즈의 최댓값은 1/√2
+        (top + v * (bottom - top)) * std::f64::consts::SQRT_2
+    }
+
+    fn simplex(&self, x: f64, y: f64) -> f64 {
+        let f2 = 0.5 * (3f64.sqrt() - 1.0);
+        let g2 = (3.0 - 3f64.sqrt()) / 6.0;
+
+        let s = (x + y) * f2;
+        let i = (x + s).floor() as i64;
+        let j = (y + s).floor() as i64;
+        let t = (i + j) as f64 * g2;
+        let x0 = x - (i as f64 - t);
+        let y0 = y - (j as f64 - t);
+
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let corners = [
+            (0, 0, x0, y0),
+            (i1, j1, x0 - i1 as f64 + g2, y0 - j1 as f64 + g2),
+            (1, 1, x0 - 1.0 + 2.0 * g2, y0 - 1.0 + 2.0 * g2),
+        ];
+
+        let mut total = 0.0;
+        for (ci, cj, dx, dy) in corners {
+            let falloff = 0.5 - dx * dx - dy * dy;
+            if falloff > 0.0 {
+                let (gx, gy) = self.gradient(i + ci, j + cj, None);
+                total += falloff.powi(4) * (gx * dx + gy * dy);
+            }
+        }
+
+        total * 99.0
+    }
+
+    fn worley(&self, x: f64, y: f64, period: Option<(i64, i64)>) -> f64 {
+        let cx = x.floor() as i64;
+        let cy = y.floor() as i64;
+
+        let mut nearest = f64::MAX;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let (wx, wy) = Self::wrap(cx + dx, cy + dy, period);
+                let h = self.hash(wx, wy);
+                let px = (cx + dx) as f64 + (h & 0xFFFF_FFFF) as f64 / u32::MAX as f64;
+                let py = (cy + dy) as f64 + (h >> 32) as f64 / u32::MAX as f64;
+                nearest = nearest.min((px - x).hypot(py - y));
+            }
+        }
+
+        nearest.min(1.0) * 2.0 - 1.0
+    }
+
+    /// Sum octaves of noise for a pixel, `cells` being the lattice cells across the image
+    fn fractal(&self, u: f64, v: f64, cells: (f64, f64), fractal: Fractal, tileable: bool) -> f64 {
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut amplitudes = 0.0;
+        let mut frequency = 1.0;
+
+        for _ in 0..fractal.octaves {
+            let mut cx = cells.0 * frequency;
+            let mut cy = cells.1 * frequency;
+            let period = if tileable {
+                cx = cx.round().max(1.0);
+                cy = cy.round().max(1.0);
+                Some((cx as i64, cy as i64))
+            } else {
+                None
+            };
+
+            let value = match (self.kind, tileable) {
+                // 심플렉스 격자는 직사각형으로 반복되지 않으므로 네 방향을 섞어서 이어붙임
+                (NoiseKind::Simplex, true) => {
+                    let a = self.sample(u * cx, v * cy, None);
+                    let b = self.sample((u - 1.0) * cx, v * cy, None);
+                    let c = self.sample(u * cx, (v - 1.0) * cy, None);
+                    let d = self.sample((u - 1.0) * cx, (v - 1.0) * cy, None);
+                    let top = a * (1.0 - u) + b * u;
+                    let bottom = c * (1.0 - u) + d * u;
+                    let blended = top * (1.0 - v) + bottom * v;
+                    // 섞으면서 줄어든 대비를 보정
+                    let weight = ((1.0 - u).powi(2) + u * u) * ((1.0 - v).powi(2) + v * v);
+                    blended / weight.sqrt()
+                }
+                _ => self.sample(u * cx, v * cy, period),
+            };
+
+            total += value * amplitude;
+            amplitudes += amplitude;
+            amplitude *= fractal.gain;
+            frequency *= fractal.lacunarity;
+        }
+
+        total / amplitudes
+    }
+}
+
+/// Make a coherent noise texture
+#[poise::command(slash_command)]
+#[allow(clippy::too_many_arguments)]
+pub async fn noise(
+    ctx: Context<'_>,
+    #[description = "the noise algorithm"] kind: NoiseKind,
+    #[description = "the width of the image"] width: usize,
+    #[description = "the height of the image"] height: usize,
+    #[description = "the size of a noise feature in pixels (default 64)"] scale: Option<f64>,
+    #[description = "the number of fractal octaves (default 1)"] octaves: Option<u32>,
+    #[description = "the frequency multiplier between octaves (default 2)"] lacunarity: Option<f64>,
+    #[description = "the amplitude multiplier between octaves (default 0.5)"] gain: Option<f64>,
+    #[description = "the colors to map the noise to"] palette: Option<NoisePalette>,
+    #[description = "make the image repeat seamlessly"] tileable: Option<bool>,
+    #[description = "the seed for the random generator, a number or any text"] seed: Option<String>,
+) -> Result<(), Error> {
+    let scale = scale.unwrap_or(64.0);
+    let fractal = Fractal {
+        octaves: octaves.unwrap_or(1),
+        lacunarity: lacunarity.unwrap_or(2.0),
+        gain: gain.unwrap_or(0.5),
+    };
+    let palette = palette.unwrap_or(NoisePalette::Grayscale);
+    let tileable = tileable.unwrap_or(false);
+
+    let error = if width > 2048 || height > 2048 || width < 1 || height < 1 {
+        Some("The width and height values must be between 1 and 2048 inclusive")
+    } else if !(1.0..=2048.0).contains(&scale) {
+        Some("The scale must be between 1 and 2048 inclusive")
+    } else if !(1..=8).contains(&fractal.octaves) {
+        Some("The octaves must be between 1 and 8 inclusive")
+    } else if !(1.0..=4.0).contains(&fractal.lacunarity) {
+        Some("The lacunarity must be between 1 and 4 inclusive")
+    } else if !(0.0..=1.0).contains(&fractal.gain) {
+        Some("The gain must be between 0 and 1 inclusive")
+    } else {
+        None
+    };
+
+    if let Some(error) = error {
+        return send_error(ctx, error.into(), false).await;
+    }
+
+    let embed = CreateEmbed::default()
+        .title("Processing!!")
+        .description("Generating the noise image");
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    let seed = Seed::from_option(seed.as_deref());
+    let noise = Noise {
+        kind,
+        seed: seed.rng().gen(),
+    };
+
+    // 백그라운드 스레드에서 이미지 생성
+    let img = task::spawn_blocking(move || {
+        let cells = (width as f64 / scale, height as f64 / scale);
+
+        RgbImage::from_fn(width as u32, height as u32, |x, y| {
+            let u = x as f64 / width as f64;
+            let v = y as f64 / height as f64;
+            let value = noise.fractal(u, v, cells, fractal, tileable);
+            palette.color((value + 1.0) / 2.0)
+        })
+    })
+    .await
+    .unwrap();
+
+    let mut buffer = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
+
+    let attachment = CreateAttachment::bytes(&*buffer, "noise.png");
+
+    let description = format!(
+        "kind : {}\nwidth : {}\nheight : {}\nscale : {}\noctaves : {}\nlacunarity : {}\ngain : {}\npalette : {}\ntileable : {}\nseed : {}",
+        kind.name(),
+        width,
+        height,
+        scale,
+        fractal.octaves,
+        fractal.lacunarity,
+        fractal.gain,
+        palette.name(),
+        tileable,
+        seed
+    );
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(description);
+
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
+
+    Ok(())
+}
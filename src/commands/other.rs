@@ -1,7 +1,8 @@
 use crate::{Context, Error};
+use arcum::seed::Seed;
 use poise::serenity_prelude::{CreateAttachment, CreateEmbed, Mentionable};
 use poise::CreateReply;
-use rand::{prelude::IteratorRandom, rngs::StdRng, seq::SliceRandom, SeedableRng};
+use rand::{prelude::IteratorRandom, seq::SliceRandom};
 use reqwest::Client;
 use serde_json;
 use tokio::time::{sleep, Duration};
@@ -32,6 +33,7 @@ pub async fn maze(
     ctx: Context<'_>,
     #[description = "Enter the width of the maze"] width: u8,
     #[description = "Enter the height of the maze"] height: u8,
+    #[description = "Enter the seed of the maze, a number or any text"] seed: Option<String>,
 ) -> Result<(), Error> {
     ctx.defer().await?;
 
@@ -50,7 +52,8 @@ pub async fn maze(
 
     let mut maze = vec![vec![1; width]; height];
 
-    let mut rng = StdRng::from_entropy();
+    let seed = Seed::from_option(seed.as_deref());
+    let mut rng = seed.rng();
 
     let start_x = 1;
     let start_y = 1;
@@ -102,6 +105,7 @@ pub async fn maze(
         .map(|row| row.join(" "))
         .collect::<Vec<String>>()
         .join("\n");
+    let maze_str = format!("{}\nseed : {}", maze_str, seed);
 
     ctx.send(CreateReply::default().content(maze_str)).await?;
 
@@ -312,36 +316,3 @@ pub async fn ping(ctx: Context<'_>) -> Result<(), Error> {
 
     Ok(())
 }
-
-/// Play Bad Apple!!
-#[poise::command(slash_command)]
-pub async fn badapple(
-    ctx: Context<'_>,
-    #[description = "Number of frames to skip"] frame_count: Option<usize>,
-) -> Result<(), Error> {
-    ctx.defer().await?;
-
-    let frame_count = frame_count.unwrap_or(1);
-
-    let mut frames = Vec::new();
-    for i in 0..=6571 / frame_count {
-        let path = format!("output/frame{}.txt", i * frame_count);
-        if let Ok(content) = std::fs::read_to_string(path) {
-            frames.push(content);
-        }
-        println!("{}", i);
-    }
-
-    let message = ctx
-        .say(frames.get(0).unwrap_or(&"".to_string()).clone())
-        .await?;
-
-    for frame in frames.iter().skip(41) {
-        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-        message
-            .edit(ctx, CreateReply::default().content(frame.clone()))
-            .await?;
-    }
-
-    Ok(())
-}
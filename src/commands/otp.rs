@@ -0,0 +1,338 @@
+use super::hash::read_input;
+use super::qr::render_matrix;
+use super::send_error;
+use crate::{Context, Error};
+use arcum::color::Color;
+use arcum::hash::MacAlgorithm;
+use arcum::otp::{self, Kind, Otp};
+use data_encoding::BASE32_NOPAD;
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed};
+use poise::CreateReply;
+use qrcode::{EcLevel, QrCode};
+use rand::rngs::OsRng;
+use std::io::Cursor;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum OtpAlgorithm {
+    #[name = "SHA1"]
+    Sha1,
+    #[name = "SHA256"]
+    Sha256,
+    #[name = "SHA512"]
+    Sha512,
+}
+
+impl OtpAlgorithm {
+    fn algorithm(self) -> MacAlgorithm {
+        match self {
+            OtpAlgorithm::Sha1 => MacAlgorithm::Sha1,
+            OtpAlgorithm::Sha256 => MacAlgorithm::Sha256,
+            OtpAlgorithm::Sha512 => MacAlgorithm::Sha512,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum OtpDigits {
+    #[name = "6"]
+    Six,
+    #[name = "8"]
+    Eight,
+}
+
+impl OtpDigits {
+    fn digits(self) -> u32 {
+        match self {
+            OtpDigits::Six => 6,
+            OtpDigits::Eight => 8,
+        }
+    }
+}
+
+/// The options given to a command, each one replacing what the secret or URI said
+struct Settings {
+    algorithm: Option<OtpAlgorithm>,
+    digits: Option<OtpDigits>,
+    period: Option<u64>,
+    counter: Option<u64>,
+    issuer: Option<String>,
+    account: Option<String>,
+}
+
+impl Settings {
+    fn apply(self, mut otp: Otp) -> Result<Otp, String> {
+        if let Some(algorithm) = self.algorithm {
+            otp.algorithm = algorithm.algorithm();
+        }
+        if let Some(digits) = self.digits {
+            otp.digits = digits.digits();
+        }
+        otp.kind = match (self.period, self.counter) {
+            (Some(_), Some(_)) => {
+                return Err("Give a period for TOTP or a counter for HOTP, not both".into())
+            }
+            (Some(period), None) => Kind::Totp { period },
+            (None, Some(counter)) => Kind::Hotp { counter },
+            (None, None) => otp.kind,
+        };
+        if self.issuer.is_some() {
+            otp.issuer = self.issuer;
+        }
+        if self.account.is_some() {
+            otp.account = self.account;
+        }
+
+        otp.check().map_err(|e| e.to_string())?;
+
+        Ok(otp)
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
+
+fn describe(otp: &Otp) -> String {
+    let kind = match otp.kind {
+        Kind::Totp { period } => format!("TOTP, a new code every {} seconds", period),
+        Kind::Hotp { counter } => format!("HOTP, counter {}", counter),
+    };
+
+    format!(
+        "type: {}\nalgorithm: {}\ndigits: {}",
+        kind,
+        otp::algorithm_name(otp.algorithm),
+        otp.digits
+    )
+}
+
+/// The QR code of the `otpauth://` URI, for scanning with an authenticator app
+fn enrolment_qr(otp: &Otp) -> Result<CreateAttachment, Error> {
+    let code = QrCode::with_error_correction_level(otp.uri().as_bytes(), EcLevel::M)?;
+    let modules: Vec<bool> = code
+        .to_colors()
+        .into_iter()
+        .map(|module| module == qrcode::Color::Dark)
+        .collect();
+    let img = render_matrix(
+        &modules,
+        code.width() as u32,
+        8,
+        4,
+        Color::rgb(0, 0, 0),
+        Color::rgb(255, 255, 255),
+    );
+
+    let mut buffer = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
+
+    Ok(CreateAttachment::bytes(buffer, "otpauth.png"))
+}
+
+/// Read the secret, from a private form if it was not given, and apply the options
+///
+/// Returns `None` when the form was not submitted or the error was already sent.
+async fn read_otp(
+    ctx: Context<'_>,
+    secret: Option<String>,
+    settings: Settings,
+) -> Result<Option<Otp>, Error> {
+    let Some(secret) = read_input(ctx, secret).await? else {
+        return Ok(None);
+    };
+
+    match Otp::parse(&secret)
+        .map_err(|e| e.to_string())
+        .and_then(|otp| settings.apply(otp))
+    {
+        Ok(otp) => Ok(Some(otp)),
+        Err(e) => {
+            send_error(ctx, e, true).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Make and check one-time passwords for authenticator apps
+#[poise::command(
+    slash_command,
+    subcommands("totp_code", "totp_verify", "totp_new"),
+    subcommand_required
+)]
+pub async fn totp(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Show the current code of a base32 secret or an otpauth:// URI
+#[poise::command(slash_command, rename = "code")]
+#[allow(clippy::too_many_arguments)]
+pub async fn totp_code(
+    ctx: Context<'_>,
+    #[description = "Base32 secret or otpauth:// URI, asked in a private form if empty"]
+    secret: Option<String>,
+    #[description = "Hash function (default SHA1)"] algorithm: Option<OtpAlgorithm>,
+    #[description = "Code length (default 6)"] digits: Option<OtpDigits>,
+    #[description = "Seconds each code lasts (default 30)"] period: Option<u64>,
+    #[description = "Counter, makes an HOTP code instead"] counter: Option<u64>,
+    #[description = "Attach a QR code for an app to scan (default false)"] qr: Option<bool>,
+    #[description = "Service name shown in the app"] issuer: Option<String>,
+    #[description = "Account name shown in the app"] account: Option<String>,
+) -> Result<(), Error> {
+    let settings = Settings {
+        algorithm,
+        digits,
+        period,
+        counter,
+        issuer,
+        account,
+    };
+    let Some(otp) = read_otp(ctx, secret, settings).await? else {
+        return Ok(());
+    };
+
+    let time = now();
+    let code = otp.code(time);
+    let mut description = format!("code: **{}**\n", code);
+    description += &match otp.remaining(time) {
+        Some(remaining) => format!(
+            "changes in: {} seconds\nnext: {}\n",
+            remaining,
+            otp.code_at(otp.counter(time) + 1)
+        ),
+        None => format!("next counter: {}\n", otp.counter(time) + 1),
+    };
+    description += &describe(&otp);
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(description);
+    let mut reply = CreateReply::default().embed(embed).ephemeral(true);
+    if qr.unwrap_or(false) {
+        reply = reply.attachment(enrolment_qr(&otp)?);
+    }
+
+    ctx.send(reply).await?;
+
+    Ok(())
+}
+
+/// Check a code against a base32 secret or an otpauth:// URI
+#[poise::command(slash_command, rename = "verify")]
+#[allow(clippy::too_many_arguments)]
+pub async fn totp_verify(
+    ctx: Context<'_>,
+    #[description = "The code to check"] code: String,
+    #[description = "Base32 secret or otpauth:// URI, asked in a private form if empty"]
+    secret: Option<String>,
+    #[description = "Steps of drift or skipped counters (default 1)"] window: Option<u64>,
+    #[description = "Hash function (default SHA1)"] algorithm: Option<OtpAlgorithm>,
+    #[description = "Code length (default 6)"] digits: Option<OtpDigits>,
+    #[description = "Seconds each code lasts (default 30)"] period: Option<u64>,
+    #[description = "Counter, checks an HOTP code instead"] counter: Option<u64>,
+) -> Result<(), Error> {
+    let window = window.unwrap_or(1);
+    if window > 10 {
+        return send_error(
+            ctx,
+            "The window must be between 0 and 10 inclusive".into(),
+            true,
+        )
+        .await;
+    }
+
+    let settings = Settings {
+        algorithm,
+        digits,
+        period,
+        counter,
+        issuer: None,
+        account: None,
+    };
+    let Some(otp) = read_otp(ctx, secret, settings).await? else {
+        return Ok(());
+    };
+
+    let time = now();
+    let (title, result) = match otp.verify(&code, time, window) {
+        Some(0) => ("Valid!!", "matches the current code".to_string()),
+        Some(steps) => match otp.kind {
+            Kind::Totp { .. } if steps < 0 => (
+                "Valid!!",
+                format!("matches the code from {} step(s) ago", -steps),
+            ),
+            Kind::Totp { .. } => (
+                "Valid!!",
+                format!("matches the code {} step(s) ahead", steps),
+            ),
+            Kind::Hotp { .. } => (
+                "Valid!!",
+                format!(
+                    "matches counter {}, use {} next",
+                    otp.counter(time) + steps as u64,
+                    otp.counter(time) + steps as u64 + 1
+                ),
+            ),
+        },
+        None => ("Invalid!!", "matches no code in the window".to_string()),
+    };
+
+    let embed = CreateEmbed::default().title(title).description(format!(
+        "code: {}\nresult: {}\nwindow: {}\n{}",
+        code.trim(),
+        result,
+        window,
+        describe(&otp)
+    ));
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Make a new random secret with a QR code to add it to an authenticator app
+#[poise::command(slash_command, rename = "new")]
+pub async fn totp_new(
+    ctx: Context<'_>,
+    #[description = "Account name shown in the app"] account: String,
+    #[description = "Service name shown in the app"] issuer: Option<String>,
+    #[description = "Hash function (default SHA1)"] algorithm: Option<OtpAlgorithm>,
+    #[description = "Code length (default 6)"] digits: Option<OtpDigits>,
+    #[description = "Seconds each code lasts (default 30)"] period: Option<u64>,
+) -> Result<(), Error> {
+    let settings = Settings {
+        algorithm,
+        digits,
+        period,
+        counter: None,
+        issuer,
+        account: Some(account),
+    };
+    let otp = match settings.apply(Otp::new(otp::generate_secret(&mut OsRng))) {
+        Ok(otp) => otp,
+        Err(e) => return send_error(ctx, e, true).await,
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(format!(
+            "secret: `{}`\nuri: `{}`\n{}\ncurrent code: {}",
+            BASE32_NOPAD.encode(&otp.secret),
+            otp.uri(),
+            describe(&otp),
+            otp.code(now())
+        ));
+
+    ctx.send(
+        CreateReply::default()
+            .embed(embed)
+            .attachment(enrolment_qr(&otp)?)
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
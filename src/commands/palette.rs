@@ -0,0 +1,359 @@
+use super::send_error;
+use crate::{Context, Error};
+use arcum::color::{self, Color};
+use arcum::font;
+use arcum::quantize;
+use arcum::seed::Seed;
+use image::{Rgb, RgbImage};
+use poise::serenity_prelude::{Attachment, CreateAttachment, CreateEmbed};
+use poise::{ChoiceParameter, CreateReply};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::io::Cursor;
+use tokio::task;
+
+const SWATCH_SIZE: u32 = 120;
+const HEADER_HEIGHT: u32 = 28;
+const TEXT_SCALE: u32 = 2;
+const MAX_FILE_SIZE: u32 = 8 * 1024 * 1024;
+/// Images are shrunk to fit in this size before quantizing
+const SAMPLE_SIZE: u32 = 256;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Scheme {
+    Complementary,
+    Analogous,
+    Triadic,
+    Tetradic,
+    Monochromatic,
+}
+
+impl Scheme {
+    const ALL: [Scheme; 5] = [
+        Scheme::Complementary,
+        Scheme::Analogous,
+        Scheme::Triadic,
+        Scheme::Tetradic,
+        Scheme::Monochromatic,
+    ];
+
+    /// Colors of the scheme, starting from the base color
+    fn colors(self, base: Color, count: usize) -> Vec<Color> {
+        let (h, s, l) = base.to_hsl();
+        let rotate = |offsets: &[f64]| {
+            offsets
+                .iter()
+                .map(|offset| Color::from_hsl(h + offset, s, l))
+                .collect()
+        };
+
+        match self {
+            Scheme::Complementary => rotate(&[0.0, 180.0]),
+            Scheme::Analogous => rotate(&[0.0, -30.0, 30.0]),
+            Scheme::Triadic => rotate(&[0.0, 120.0, 240.0]),
+            Scheme::Tetradic => rotate(&[0.0, 60.0, 180.0, 240.0]),
+            Scheme::Monochromatic => {
+                // 밝기를 고르게 나누고 원래 색에 가장 가까운 단계를 원래 색으로 바꿈
+                let lightness: Vec<f64> = (0..count)
+                    .map(|i| 0.15 + 0.75 * i as f64 / (count - 1) as f64)
+                    .collect();
+                let closest = (0..count)
+                    .min_by(|&a, &b| {
+                        (lightness[a] - l)
+                            .abs()
+                            .total_cmp(&(lightness[b] - l).abs())
+                    })
+                    .unwrap();
+
+                (0..count)
+                    .map(|i| {
+                        if i == closest {
+                            base
+                        } else {
+                            Color::from_hsl(h, s, lightness[i])
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Quantizer {
+    #[name = "k-means"]
+    KMeans,
+    #[name = "median cut"]
+    MedianCut,
+}
+
+/// A color that is neither washed out, too dark nor too bright
+fn random_color(rng: &mut impl Rng) -> Color {
+    Color::from_hsl(
+        rng.gen_range(0.0..360.0),
+        rng.gen_range(0.45..0.8),
+        rng.gen_range(0.45..0.65),
+    )
+}
+
+/// Colors with close hues, similar saturation and evenly spread lightness,
+/// from the darkest to the brightest
+fn random_palette(rng: &mut impl Rng, count: usize) -> Vec<Color> {
+    let hue = rng.gen_range(0.0..360.0);
+    let spread = rng.gen_range(60.0..160.0);
+    let saturation = rng.gen_range(0.45..0.8);
+
+    (0..count)
+        .map(|i| {
+            let t = i as f64 / (count - 1) as f64;
+            Color::from_hsl(
+                hue + spread * (t - 0.5),
+                (saturation + rng.gen_range(-0.1f64..0.1)).clamp(0.0, 1.0),
+                0.25 + 0.6 * t + rng.gen_range(-0.04..0.04),
+            )
+        })
+        .collect()
+}
+
+/// Every row as a header with its name above a strip of swatches,
+/// each swatch with its label lines at the bottom
+pub(crate) fn render(rows: &[(String, Vec<(Color, String)>)]) -> RgbImage {
+    let columns = rows
+        .iter()
+        .map(|(_, colors)| colors.len())
+        .max()
+        .unwrap_or(1) as u32;
+    let header_width = rows
+        .iter()
+        .map(|(name, _)| font::text_width(name, TEXT_SCALE) + 16)
+        .max()
+        .unwrap_or(0);
+    let width = (columns * SWATCH_SIZE).max(header_width);
+    let height = rows.len() as u32 * (HEADER_HEIGHT + SWATCH_SIZE);
+
+    let mut img = RgbImage::from_pixel(width, height, Rgb([255, 255, 255]));
+    let text_height = font::text_height(TEXT_SCALE);
+
+    for (row, (name, colors)) in rows.iter().enumerate() {
+        let top = row as u32 * (HEADER_HEIGHT + SWATCH_SIZE);
+        font::draw_text(
+            &mut img,
+            8,
+            (top + (HEADER_HEIGHT - text_height) / 2) as i64,
+            name,
+            TEXT_SCALE,
+            Rgb([51, 51, 51]),
+        );
+
+        for (column, (color, label)) in colors.iter().enumerate() {
+            let left = column as u32 * SWATCH_SIZE;
+            for y in 0..SWATCH_SIZE {
+                for x in 0..SWATCH_SIZE {
+                    img.put_pixel(
+                        left + x,
+                        top + HEADER_HEIGHT + y,
+                        Rgb([color.r, color.g, color.b]),
+                    );
+                }
+            }
+
+            let text = color.text_color();
+            for (line, label) in label.lines().rev().enumerate() {
+                font::draw_text(
+                    &mut img,
+                    left as i64
+                        + (SWATCH_SIZE as i64 - font::text_width(label, TEXT_SCALE) as i64) / 2,
+                    (top + HEADER_HEIGHT + SWATCH_SIZE - (line as u32 + 1) * (text_height + 6) - 2)
+                        as i64,
+                    label,
+                    TEXT_SCALE,
+                    Rgb([text.r, text.g, text.b]),
+                );
+            }
+        }
+    }
+
+    img
+}
+
+/// Generate color schemes from a base color, or a random palette
+#[poise::command(slash_command)]
+pub async fn palette(
+    ctx: Context<'_>,
+    #[description = "the base color, like hexcode (random if empty)"] color: Option<String>,
+    #[description = "the color scheme (every scheme if empty)"] scheme: Option<Scheme>,
+    #[description = "the number of colors of monochromatic and random palettes (default 5)"]
+    count: Option<usize>,
+    #[description = "the seed for random colors, a number or any text"] seed: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let count = count.unwrap_or(5);
+
+    let base = match color.as_deref().map(Color::parse) {
+        Some(Ok(base)) => Some(Color { a: 255, ..base }),
+        Some(Err(e)) => {
+            return send_error(
+                ctx,
+                format!("{}\nAccepted formats : {}", e, color::FORMATS),
+                false,
+            )
+            .await;
+        }
+        None => None,
+    };
+
+    if !(2..=12).contains(&count) {
+        return send_error(
+            ctx,
+            "The count must be between 2 and 12 inclusive".into(),
+            false,
+        )
+        .await;
+    }
+
+    let seed = Seed::from_option(seed.as_deref());
+    let mut rng = seed.rng();
+    let row = |scheme: Scheme, base: Color| (scheme.name().to_string(), scheme.colors(base, count));
+
+    let rows: Vec<(String, Vec<Color>)> = match (base, scheme) {
+        (Some(base), Some(scheme)) => vec![row(scheme, base)],
+        (Some(base), None) => Scheme::ALL
+            .iter()
+            .map(|&scheme| row(scheme, base))
+            .collect(),
+        (None, Some(scheme)) => vec![row(scheme, random_color(&mut rng))],
+        (None, None) => vec![("Random".to_string(), random_palette(&mut rng, count))],
+    };
+
+    let mut description: String = rows
+        .iter()
+        .map(|(name, colors)| {
+            let hex_codes: Vec<String> = colors.iter().map(|color| color.to_hex()).collect();
+            format!("{} : {}\n", name.to_lowercase(), hex_codes.join(" "))
+        })
+        .collect();
+    // 기준 색이 없을 때만 시드를 사용
+    if base.is_none() {
+        description.push_str(&format!("seed : {}", seed));
+    }
+
+    let rows: Vec<(String, Vec<(Color, String)>)> = rows
+        .into_iter()
+        .map(|(name, colors)| {
+            let labeled = colors
+                .iter()
+                .map(|&color| (color, color.to_hex()))
+                .collect();
+            (name, labeled)
+        })
+        .collect();
+
+    // 백그라운드 스레드에서 이미지 생성
+    let img = task::spawn_blocking(move || render(&rows)).await.unwrap();
+
+    let mut buffer = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
+
+    let attachment = CreateAttachment::bytes(&*buffer, "palette.png");
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(description);
+
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
+
+    Ok(())
+}
+
+/// Find the dominant colors of an image
+#[poise::command(slash_command)]
+pub async fn colors(
+    ctx: Context<'_>,
+    #[description = "the image"] file: Attachment,
+    #[description = "the number of colors (default 5)"] count: Option<usize>,
+    #[description = "the quantization method (default k-means)"] method: Option<Quantizer>,
+) -> Result<(), Error> {
+    let count = count.unwrap_or(5);
+    let method = method.unwrap_or(Quantizer::KMeans);
+
+    let error = if !(1..=12).contains(&count) {
+        Some("The count must be between 1 and 12 inclusive")
+    } else if file.size > MAX_FILE_SIZE {
+        Some("The file must be 8 MiB or smaller")
+    } else {
+        None
+    };
+
+    if let Some(error) = error {
+        return send_error(ctx, error.into(), false).await;
+    }
+
+    ctx.defer().await?;
+
+    let data = file.download().await?;
+
+    // 디코딩과 양자화는 백그라운드 스레드에서 처리
+    let colors = task::spawn_blocking(move || -> Result<Vec<(Color, f64)>, image::ImageError> {
+        let img = image::load_from_memory(&data)?
+            .thumbnail(SAMPLE_SIZE, SAMPLE_SIZE)
+            .to_rgba8();
+
+        // 투명한 픽셀은 무시
+        let pixels: Vec<Color> = img
+            .pixels()
+            .filter(|pixel| pixel[3] >= 128)
+            .map(|pixel| Color::rgb(pixel[0], pixel[1], pixel[2]))
+            .collect();
+
+        Ok(match method {
+            // 같은 이미지에는 항상 같은 결과가 나오도록 고정된 시드 사용
+            Quantizer::KMeans => {
+                quantize::kmeans(&pixels, count, &mut ChaCha8Rng::seed_from_u64(0))
+            }
+            Quantizer::MedianCut => quantize::median_cut(&pixels, count),
+        })
+    })
+    .await?;
+
+    let colors = match colors {
+        Ok(colors) if !colors.is_empty() => colors,
+        Ok(_) => {
+            return send_error(ctx, "The image has no opaque pixels".into(), false).await;
+        }
+        Err(e) => {
+            return send_error(ctx, format!("Failed to decode the image\n{}", e), false).await;
+        }
+    };
+
+    let description: String = colors
+        .iter()
+        .map(|(color, share)| format!("{} : {:.1}%\n", color, share * 100.0))
+        .chain([format!("method : {}", method.name())])
+        .collect();
+
+    let rows = vec![(
+        "Dominant colors".to_string(),
+        colors
+            .iter()
+            .map(|&(color, share)| (color, format!("{}\n{:.1}%", color, share * 100.0)))
+            .collect(),
+    )];
+
+    let img = task::spawn_blocking(move || render(&rows)).await.unwrap();
+
+    let mut buffer = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
+
+    let attachment = CreateAttachment::bytes(&*buffer, "colors.png");
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(description);
+
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
+
+    Ok(())
+}
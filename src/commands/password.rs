@@ -0,0 +1,187 @@
+use super::hash::read_input;
+use super::{code_block, send_error, CODE_BLOCK_OVERHEAD, MAX_DESCRIPTION};
+use crate::{Context, Error};
+use arcum::password::{self, DIGITS, LOWERCASE, SYMBOLS, UPPERCASE};
+use poise::serenity_prelude::CreateEmbed;
+use poise::{ChoiceParameter, CreateReply};
+use rand::rngs::OsRng;
+use tokio::task;
+
+/// Attackers and how many guesses per second they can make
+const ATTACKS: [(&str, f64); 4] = [
+    ("online, throttled (100 per hour)", 100.0 / 3600.0),
+    ("online, unthrottled (10 per second)", 10.0),
+    ("offline, slow hash like argon2 (10k per second)", 1e4),
+    ("offline, fast hash like SHA-256 (10B per second)", 1e10),
+];
+
+const SCORES: [&str; 5] = [
+    "too guessable",
+    "very guessable",
+    "somewhat guessable",
+    "safely unguessable",
+    "very unguessable",
+];
+
+/// Check or generate passwords
+#[poise::command(
+    slash_command,
+    subcommands("password_check", "password_generate"),
+    subcommand_required
+)]
+pub async fn password(_: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Estimate how hard a password is to guess
+#[poise::command(slash_command, rename = "check")]
+pub async fn password_check(
+    ctx: Context<'_>,
+    #[description = "The password, asked in a private form if empty"] password: Option<String>,
+) -> Result<(), Error> {
+    let Some(password) = read_input(ctx, password).await? else {
+        return Ok(());
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let length = password.chars().count();
+    let estimate = task::spawn_blocking(move || password::estimate(&password)).await?;
+
+    let mut description = format!(
+        "score: {}/4, {}\nguesses: {:.2e} ({:.1} bits)\nlength: {} characters",
+        estimate.score(),
+        SCORES[estimate.score() as usize],
+        estimate.guesses,
+        estimate.bits(),
+        length
+    );
+    if length > password::MAX_LENGTH {
+        description += &format!(" (only the first {} were checked)", password::MAX_LENGTH);
+    }
+
+    description += "\n\n**time to crack**";
+    for (attack, speed) in ATTACKS {
+        description += &format!(
+            "\n{}: {}",
+            attack,
+            password::describe_seconds(estimate.crack_seconds(speed))
+        );
+    }
+
+    // 비밀번호에 백틱이 있어도 마크다운이 깨지지 않도록 코드 블록에 넣음
+    description += "\n\n**patterns**\n";
+    let patterns: Vec<String> = estimate
+        .matches
+        .iter()
+        .map(|found| format!("{}: {}", found.token, found.pattern))
+        .collect();
+    let limit = MAX_DESCRIPTION.saturating_sub(description.chars().count() + CODE_BLOCK_OVERHEAD);
+    description += &code_block(&patterns.join("\n"), limit);
+
+    let embed = CreateEmbed::default()
+        .title("Checked!!")
+        .description(description);
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum PasswordKind {
+    #[name = "random characters"]
+    Characters,
+    #[name = "passphrase"]
+    Passphrase,
+}
+
+/// Make a random password or a diceware-style passphrase
+#[poise::command(slash_command, rename = "generate")]
+#[allow(clippy::too_many_arguments)]
+pub async fn password_generate(
+    ctx: Context<'_>,
+    #[description = "Characters or words (default random characters)"] kind: Option<PasswordKind>,
+    #[description = "Characters (default 20) or words (default 6)"] length: Option<usize>,
+    #[description = "Use lowercase letters (default true)"] lowercase: Option<bool>,
+    #[description = "Use uppercase letters (default true)"] uppercase: Option<bool>,
+    #[description = "Use digits (default true)"] digits: Option<bool>,
+    #[description = "Use symbols (default true)"] symbols: Option<bool>,
+    #[description = "Between the words of a passphrase (default -)"] separator: Option<String>,
+) -> Result<(), Error> {
+    let kind = kind.unwrap_or(PasswordKind::Characters);
+    let separator = separator.unwrap_or_else(|| "-".into());
+
+    let (generated, bits) = match kind {
+        PasswordKind::Characters => {
+            let length = length.unwrap_or(20);
+            let classes: Vec<&str> = [
+                (lowercase, LOWERCASE),
+                (uppercase, UPPERCASE),
+                (digits, DIGITS),
+                (symbols, SYMBOLS),
+            ]
+            .into_iter()
+            .filter(|(used, _)| used.unwrap_or(true))
+            .map(|(_, class)| class)
+            .collect();
+
+            if classes.is_empty() {
+                return send_error(ctx, "Choose at least one kind of character".into(), true).await;
+            }
+            if !(4..=128).contains(&length) {
+                return send_error(
+                    ctx,
+                    "The length must be between 4 and 128 characters inclusive".into(),
+                    true,
+                )
+                .await;
+            }
+
+            let pool: usize = classes.iter().map(|class| class.len()).sum();
+            (
+                password::generate(&mut OsRng, length, &classes),
+                length as f64 * (pool as f64).log2(),
+            )
+        }
+        PasswordKind::Passphrase => {
+            let words = length.unwrap_or(6);
+            if !(3..=24).contains(&words) {
+                return send_error(
+                    ctx,
+                    "The length must be between 3 and 24 words inclusive".into(),
+                    true,
+                )
+                .await;
+            }
+            if separator.chars().count() > 5 || separator.contains('`') {
+                return send_error(
+                    ctx,
+                    "The separator must be at most 5 characters without backticks".into(),
+                    true,
+                )
+                .await;
+            }
+
+            (
+                password::passphrase(&mut OsRng, words, &separator),
+                words as f64 * 11.0,
+            )
+        }
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(format!(
+            "```\n{}\n```\nkind: {}\nentropy: about {:.0} bits",
+            generated,
+            kind.name(),
+            bits
+        ));
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
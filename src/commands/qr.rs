@@ -0,0 +1,456 @@
+use super::{code_block, send_error, CODE_BLOCK_OVERHEAD, MAX_DESCRIPTION};
+use crate::{Context, Error};
+use arcum::barcode;
+use arcum::color::{self, Color, ParseColorError};
+use arcum::font;
+use arcum::qrdecode::{self, Decoded};
+use image::imageops::FilterType;
+use image::{GrayImage, Luma, RgbaImage};
+use poise::serenity_prelude::{Attachment, CreateAttachment, CreateEmbed};
+use poise::{ChoiceParameter, CreateReply};
+use qrcode::{EcLevel, QrCode};
+use std::io::Cursor;
+use tokio::task;
+
+/// Largest width or height of a generated image
+const MAX_IMAGE_SIZE: u32 = 4096;
+const MAX_FILE_SIZE: u32 = 8 * 1024 * 1024;
+/// Uploaded images are shrunk to fit in this size before looking for codes
+const MAX_SCAN_SIZE: u32 = 2048;
+/// Most codes listed in one reply, the rest are only counted
+const MAX_LISTED: usize = 10;
+/// Longest link host shown above a code
+const MAX_HOST_SHOWN: usize = 100;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum ErrorCorrection {
+    #[name = "L (7%)"]
+    Low,
+    #[name = "M (15%)"]
+    Medium,
+    #[name = "Q (25%)"]
+    Quartile,
+    #[name = "H (30%)"]
+    High,
+}
+
+impl ErrorCorrection {
+    fn level(self) -> EcLevel {
+        match self {
+            ErrorCorrection::Low => EcLevel::L,
+            ErrorCorrection::Medium => EcLevel::M,
+            ErrorCorrection::Quartile => EcLevel::Q,
+            ErrorCorrection::High => EcLevel::H,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum BarcodeFormat {
+    #[name = "Code 128"]
+    Code128,
+    #[name = "EAN-13"]
+    Ean13,
+}
+
+/// The foreground and background colors, black on white by default
+fn parse_colors(
+    foreground: Option<&str>,
+    background: Option<&str>,
+) -> Result<(Color, Color), ParseColorError> {
+    let parse = |input: Option<&str>, default: Color| match input {
+        Some(input) => Color::parse(input),
+        None => Ok(default),
+    };
+
+    Ok((
+        parse(foreground, Color::rgb(0, 0, 0))?,
+        parse(background, Color::rgb(255, 255, 255))?,
+    ))
+}
+
+/// Draw a square matrix of modules with a quiet zone around it
+pub(crate) fn render_matrix(
+    modules: &[bool],
+    width: u32,
+    module_size: u32,
+    quiet_zone: u32,
+    foreground: Color,
+    background: Color,
+) -> RgbaImage {
+    let size = (width + quiet_zone * 2) * module_size;
+
+    RgbaImage::from_fn(size, size, |x, y| {
+        let column = (x / module_size).checked_sub(quiet_zone);
+        let row = (y / module_size).checked_sub(quiet_zone);
+        let dark = match (column, row) {
+            (Some(column), Some(row)) if column < width && row < width => {
+                modules[(row * width + column) as usize]
+            }
+            _ => false,
+        };
+
+        if dark {
+            foreground.into()
+        } else {
+            background.into()
+        }
+    })
+}
+
+/// Draw a row of bars with quiet zones on both sides and an optional caption below
+fn render_bars(
+    modules: &[bool],
+    module_size: u32,
+    height: u32,
+    quiet_zone: u32,
+    foreground: Color,
+    background: Color,
+    caption: Option<&str>,
+) -> RgbaImage {
+    let width = (modules.len() as u32 + quiet_zone * 2) * module_size;
+    let caption_height = match caption {
+        Some(_) => font::text_height(module_size) + module_size * 4,
+        None => 0,
+    };
+
+    let mut img = RgbaImage::from_fn(width, height + caption_height, |x, y| {
+        let module = (x / module_size).checked_sub(quiet_zone);
+        let dark =
+            y < height && module.is_some_and(|module| modules.get(module as usize) == Some(&true));
+
+        if dark {
+            foreground.into()
+        } else {
+            background.into()
+        }
+    });
+
+    if let Some(caption) = caption {
+        let text_width = font::text_width(caption, module_size);
+        font::draw_text(
+            &mut img,
+            (width as i64 - text_width as i64) / 2,
+            (height + module_size * 2) as i64,
+            caption,
+            module_size,
+            foreground.into(),
+        );
+    }
+
+    img
+}
+
+/// Encode text or a link into a QR code
+#[poise::command(slash_command)]
+pub async fn qr(
+    ctx: Context<'_>,
+    #[description = "the text or link to encode"] text: String,
+    #[description = "the error correction level (default M)"] level: Option<ErrorCorrection>,
+    #[description = "the size of a module in pixels (default 8)"] module_size: Option<u32>,
+    #[description = "the empty border in modules (default 4)"] quiet_zone: Option<u32>,
+    #[description = "the module color, like hexcode (default black)"] foreground: Option<String>,
+    #[description = "the background, like hexcode (default white)"] background: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let level = level.unwrap_or(ErrorCorrection::Medium);
+    let module_size = module_size.unwrap_or(8);
+    let quiet_zone = quiet_zone.unwrap_or(4);
+
+    let error = if !(1..=32).contains(&module_size) {
+        Some("The module size must be between 1 and 32 inclusive")
+    } else if quiet_zone > 16 {
+        Some("The quiet zone must be between 0 and 16 inclusive")
+    } else {
+        None
+    };
+
+    if let Some(error) = error {
+        return send_error(ctx, error.into(), false).await;
+    }
+
+    let (foreground, background) = match parse_colors(foreground.as_deref(), background.as_deref())
+    {
+        Ok(colors) => colors,
+        Err(e) => {
+            return send_error(
+                ctx,
+                format!("{}\nAccepted formats : {}", e, color::FORMATS),
+                false,
+            )
+            .await
+        }
+    };
+
+    let code = match QrCode::with_error_correction_level(text.as_bytes(), level.level()) {
+        Ok(code) => code,
+        Err(e) => {
+            return send_error(ctx, format!("Failed to make the QR code\n{}", e), false).await
+        }
+    };
+
+    let width = code.width() as u32;
+    if (width + quiet_zone * 2) * module_size > MAX_IMAGE_SIZE {
+        return send_error(
+            ctx,
+            format!(
+                "The image would be larger than {0}×{0}, use a smaller module size",
+                MAX_IMAGE_SIZE
+            ),
+            false,
+        )
+        .await;
+    }
+
+    let modules: Vec<bool> = code
+        .to_colors()
+        .into_iter()
+        .map(|module| module == qrcode::Color::Dark)
+        .collect();
+    let img = render_matrix(
+        &modules,
+        width,
+        module_size,
+        quiet_zone,
+        foreground,
+        background,
+    );
+
+    let mut buffer = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
+
+    let attachment = CreateAttachment::bytes(&*buffer, "qr.png");
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(format!(
+            "error correction : {}\nmodules : {}×{}\nforeground : {}\nbackground : {}",
+            level.name(),
+            width,
+            width,
+            foreground,
+            background
+        ));
+
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
+
+    Ok(())
+}
+
+/// Encode text into a Code 128 or EAN-13 barcode
+#[poise::command(slash_command)]
+#[allow(clippy::too_many_arguments)]
+pub async fn barcode(
+    ctx: Context<'_>,
+    #[description = "the text, or 12 or 13 digits for EAN-13"] text: String,
+    #[description = "the barcode format (default Code 128)"] format: Option<BarcodeFormat>,
+    #[description = "the narrowest bar width in pixels (default 3)"] module_size: Option<u32>,
+    #[description = "the height of the bars in pixels (default 100)"] height: Option<u32>,
+    #[description = "the empty border in modules (default 10)"] quiet_zone: Option<u32>,
+    #[description = "the bar color, like hexcode (default black)"] foreground: Option<String>,
+    #[description = "the background, like hexcode (default white)"] background: Option<String>,
+    #[description = "write the text below the bars (default true)"] caption: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer().await?;
+
+    let format = format.unwrap_or(BarcodeFormat::Code128);
+    let module_size = module_size.unwrap_or(3);
+    let height = height.unwrap_or(100);
+    let quiet_zone = quiet_zone.unwrap_or(10);
+    let caption = caption.unwrap_or(true);
+
+    let error = if !(1..=16).contains(&module_size) {
+        Some("The module size must be between 1 and 16 inclusive")
+    } else if !(10..=1000).contains(&height) {
+        Some("The height must be between 10 and 1000 inclusive")
+    } else if quiet_zone > 50 {
+        Some("The quiet zone must be between 0 and 50 inclusive")
+    } else {
+        None
+    };
+
+    if let Some(error) = error {
+        return send_error(ctx, error.into(), false).await;
+    }
+
+    let (foreground, background) = match parse_colors(foreground.as_deref(), background.as_deref())
+    {
+        Ok(colors) => colors,
+        Err(e) => {
+            return send_error(
+                ctx,
+                format!("{}\nAccepted formats : {}", e, color::FORMATS),
+                false,
+            )
+            .await
+        }
+    };
+
+    let encoded = match format {
+        BarcodeFormat::Code128 => barcode::code128(&text).map(|modules| (modules, text.clone())),
+        BarcodeFormat::Ean13 => barcode::ean13(&text),
+    };
+    let (modules, text) = match encoded {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            return send_error(ctx, format!("Failed to make the barcode\n{}", e), false).await
+        }
+    };
+
+    if (modules.len() as u32 + quiet_zone * 2) * module_size > MAX_IMAGE_SIZE {
+        return send_error(
+            ctx,
+            format!(
+                "The image would be wider than {}, use shorter text or a smaller module size",
+                MAX_IMAGE_SIZE
+            ),
+            false,
+        )
+        .await;
+    }
+
+    let img = render_bars(
+        &modules,
+        module_size,
+        height,
+        quiet_zone,
+        foreground,
+        background,
+        caption.then_some(text.as_str()),
+    );
+
+    let mut buffer = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buffer), image::ImageOutputFormat::Png)?;
+
+    let attachment = CreateAttachment::bytes(&*buffer, "barcode.png");
+
+    let embed = CreateEmbed::default()
+        .title("Generated!!")
+        .description(format!(
+            "format : {}\ntext : {}\nmodules : {}",
+            format.name(),
+            text,
+            modules.len()
+        ));
+
+    ctx.send(CreateReply::default().embed(embed).attachment(attachment))
+        .await?;
+
+    Ok(())
+}
+
+/// The host of an http or https link, without any user info before it
+fn link_host(text: &str) -> Option<&str> {
+    let text = text.trim();
+    let rest = ["http://", "https://"].iter().find_map(|scheme| {
+        text.get(..scheme.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(scheme))
+            .map(|_| &text[scheme.len()..])
+    })?;
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    // `https://trusted.com@other.com`은 실제로 other.com으로 연결됨
+    let host = authority.rsplit('@').next().unwrap_or_default();
+
+    (!host.is_empty()).then_some(host)
+}
+
+/// The heading shown above the text of a code
+fn describe(index: usize, code: &Decoded) -> String {
+    let link = match link_host(&code.text) {
+        Some(host) if host.chars().count() > MAX_HOST_SHOWN => format!(
+            "\nlink to : {}…",
+            host.chars().take(MAX_HOST_SHOWN).collect::<String>()
+        ),
+        Some(host) => format!("\nlink to : {}", host),
+        None => String::new(),
+    };
+
+    format!(
+        "**{}.** version {}, error correction {:?}{}\n",
+        index + 1,
+        code.version,
+        code.level,
+        link
+    )
+}
+
+/// Read the QR codes in an image
+#[poise::command(slash_command)]
+pub async fn qrread(
+    ctx: Context<'_>,
+    #[description = "the image with QR codes"] file: Attachment,
+) -> Result<(), Error> {
+    if file.size > MAX_FILE_SIZE {
+        return send_error(ctx, "The file must be 8 MiB or smaller".into(), false).await;
+    }
+
+    ctx.defer().await?;
+
+    let data = file.download().await?;
+
+    // 디코딩과 탐색은 백그라운드 스레드에서 처리
+    let codes = task::spawn_blocking(move || -> Result<Vec<Decoded>, image::ImageError> {
+        let img = image::load_from_memory(&data)?;
+        let img = if img.width() > MAX_SCAN_SIZE || img.height() > MAX_SCAN_SIZE {
+            img.resize(MAX_SCAN_SIZE, MAX_SCAN_SIZE, FilterType::Triangle)
+        } else {
+            img
+        }
+        .to_rgba8();
+
+        // 투명한 배경은 흰색으로 봄
+        let gray = GrayImage::from_fn(img.width(), img.height(), |x, y| {
+            let [r, g, b, a] = img.get_pixel(x, y).0;
+            let luma = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+            Luma([((luma * a as u32 + 255 * (255 - a as u32)) / 255) as u8])
+        });
+
+        Ok(qrdecode::decode(&gray))
+    })
+    .await?;
+
+    let codes = match codes {
+        Ok(codes) if !codes.is_empty() => codes,
+        Ok(_) => return send_error(ctx, "No QR code was found in the image".into(), false).await,
+        Err(e) => {
+            return send_error(ctx, format!("Failed to decode the image\n{}", e), false).await
+        }
+    };
+
+    let headings: Vec<String> = codes
+        .iter()
+        .take(MAX_LISTED)
+        .enumerate()
+        .map(|(index, code)| describe(index, code))
+        .collect();
+    let unlisted = match codes.len() - headings.len() {
+        0 => String::new(),
+        count => format!("and {} more not listed", count),
+    };
+
+    // 임베드 설명 길이 제한에 맞게 제목과 코드 블록 틀을 빼고 남은 길이를 코드마다 나눔
+    let fixed: usize = headings
+        .iter()
+        .map(|heading| heading.chars().count() + CODE_BLOCK_OVERHEAD + 1)
+        .sum::<usize>()
+        + unlisted.chars().count();
+    let limit = MAX_DESCRIPTION.saturating_sub(fixed) / headings.len();
+
+    let mut description: String = headings
+        .iter()
+        .zip(&codes)
+        .map(|(heading, code)| format!("{}{}\n", heading, code_block(&code.text, limit)))
+        .collect();
+    description += &unlisted;
+
+    let embed = CreateEmbed::default()
+        .title("Decoded!!")
+        .description(description);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
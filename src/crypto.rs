@@ -0,0 +1,405 @@
+//! Passphrase encryption and Ed25519 signatures for passing small secrets around
+//!
+//! An encrypted message is a header followed by the ciphertext and its tag:
+//!
+//! | bytes | content |
+//! | --- | --- |
+//! | 4 | `arcm` |
+//! | 1 | format version, 1 |
+//! | 1 | [`Cipher`] |
+//! | 12 | argon2id memory in KiB, time and parallelism as big-endian `u32`s |
+//! | 16 | salt |
+//! | 12 | nonce |
+//!
+//! The whole header is authenticated along with the ciphertext, so changing the
+//! parameters makes decryption fail like a wrong passphrase does.
+
+use aes_gcm::Aes256Gcm;
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::ChaCha20Poly1305;
+use data_encoding::BASE64;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::{CryptoRng, Rng};
+use std::fmt;
+
+const MAGIC: &[u8; 4] = b"arcm";
+const VERSION: u8 = 1;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+const HEADER_LENGTH: usize = 4 + 1 + 1 + 12 + SALT_LENGTH + NONCE_LENGTH;
+
+/// Largest argon2 parameters accepted when decrypting, so a crafted message cannot
+/// make the bot spend minutes or gigabytes deriving the key
+const MAX_MEMORY: u32 = 262144;
+const MAX_TIME: u32 = 10;
+const MAX_PARALLELISM: u32 = 8;
+
+const ARMOR_BEGIN: &str = "-----BEGIN ARCUM MESSAGE-----";
+const ARMOR_END: &str = "-----END ARCUM MESSAGE-----";
+/// Characters per line of armored base64
+const ARMOR_WIDTH: usize = 64;
+
+#[derive(Debug)]
+pub struct CryptoError(String);
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+fn error(message: impl Into<String>) -> CryptoError {
+    CryptoError(message.into())
+}
+
+/// Authenticated cipher with a 256-bit key and a 96-bit nonce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cipher {
+    ChaCha20Poly1305,
+    Aes256Gcm,
+}
+
+impl Cipher {
+    pub fn name(self) -> &'static str {
+        match self {
+            Cipher::ChaCha20Poly1305 => "ChaCha20-Poly1305",
+            Cipher::Aes256Gcm => "AES-256-GCM",
+        }
+    }
+
+    fn id(self) -> u8 {
+        match self {
+            Cipher::ChaCha20Poly1305 => 1,
+            Cipher::Aes256Gcm => 2,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Cipher::ChaCha20Poly1305),
+            2 => Some(Cipher::Aes256Gcm),
+            _ => None,
+        }
+    }
+
+    fn seal(self, key: &[u8; 32], nonce: &[u8], payload: Payload) -> Vec<u8> {
+        // 논스가 매번 새로 만들어지므로 암호화는 실패하지 않음
+        match self {
+            Cipher::ChaCha20Poly1305 => ChaCha20Poly1305::new(key.into())
+                .encrypt(nonce.into(), payload)
+                .unwrap(),
+            Cipher::Aes256Gcm => Aes256Gcm::new(key.into())
+                .encrypt(nonce.into(), payload)
+                .unwrap(),
+        }
+    }
+
+    fn open(self, key: &[u8; 32], nonce: &[u8], payload: Payload) -> Option<Vec<u8>> {
+        match self {
+            Cipher::ChaCha20Poly1305 => ChaCha20Poly1305::new(key.into())
+                .decrypt(nonce.into(), payload)
+                .ok(),
+            Cipher::Aes256Gcm => Aes256Gcm::new(key.into())
+                .decrypt(nonce.into(), payload)
+                .ok(),
+        }
+    }
+}
+
+/// Argon2id parameters of an encrypted message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyParams {
+    /// In KiB
+    pub memory: u32,
+    pub time: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KeyParams {
+    fn default() -> Self {
+        KeyParams {
+            memory: argon2::Params::DEFAULT_M_COST,
+            time: argon2::Params::DEFAULT_T_COST,
+            parallelism: argon2::Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KeyParams {
+    fn derive(self, passphrase: &[u8], salt: &[u8]) -> Result<[u8; 32], CryptoError> {
+        let params = argon2::Params::new(self.memory, self.time, self.parallelism, Some(32))
+            .map_err(|e| error(format!("Invalid key parameters, {}", e)))?;
+        let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+        let mut key = [0; 32];
+        argon2
+            .hash_password_into(passphrase, salt, &mut key)
+            .map_err(|e| error(format!("Failed to derive the key, {}", e)))?;
+
+        Ok(key)
+    }
+}
+
+/// What [`decrypt`] found out about a message besides its content
+#[derive(Debug, Clone, Copy)]
+pub struct Decrypted {
+    pub cipher: Cipher,
+    pub params: KeyParams,
+}
+
+/// Encrypt with a key derived from the passphrase, see the module docs for the layout
+pub fn encrypt(
+    rng: &mut (impl Rng + CryptoRng),
+    plaintext: &[u8],
+    passphrase: &str,
+    cipher: Cipher,
+    params: KeyParams,
+) -> Result<Vec<u8>, CryptoError> {
+    let mut salt = [0; SALT_LENGTH];
+    let mut nonce = [0; NONCE_LENGTH];
+    rng.fill(&mut salt);
+    rng.fill(&mut nonce);
+
+    let key = params.derive(passphrase.as_bytes(), &salt)?;
+
+    let mut message = Vec::with_capacity(HEADER_LENGTH + plaintext.len() + 16);
+    message.extend_from_slice(MAGIC);
+    message.push(VERSION);
+    message.push(cipher.id());
+    for value in [params.memory, params.time, params.parallelism] {
+        message.extend_from_slice(&value.to_be_bytes());
+    }
+    message.extend_from_slice(&salt);
+    message.extend_from_slice(&nonce);
+
+    let ciphertext = cipher.seal(
+        &key,
+        &nonce,
+        Payload {
+            msg: plaintext,
+            aad: &message,
+        },
+    );
+    message.extend_from_slice(&ciphertext);
+
+    Ok(message)
+}
+
+/// Decrypt a message from [`encrypt`]
+pub fn decrypt(message: &[u8], passphrase: &str) -> Result<(Vec<u8>, Decrypted), CryptoError> {
+    if message.len() < HEADER_LENGTH + 16 || &message[..4] != MAGIC {
+        return Err(error("This is not an encrypted message"));
+    }
+    if message[4] != VERSION {
+        return Err(error(format!(
+            "Unknown message version {}, expected {}",
+            message[4], VERSION
+        )));
+    }
+    let cipher = Cipher::from_id(message[5])
+        .ok_or_else(|| error(format!("Unknown cipher {}", message[5])))?;
+
+    let number = |index: usize| u32::from_be_bytes(message[index..index + 4].try_into().unwrap());
+    let params = KeyParams {
+        memory: number(6),
+        time: number(10),
+        parallelism: number(14),
+    };
+    if params.memory > MAX_MEMORY || params.time > MAX_TIME || params.parallelism > MAX_PARALLELISM
+    {
+        return Err(error(format!(
+            "The key parameters are too costly, at most {} KiB, {} passes and {} lanes",
+            MAX_MEMORY, MAX_TIME, MAX_PARALLELISM
+        )));
+    }
+
+    let (header, ciphertext) = message.split_at(HEADER_LENGTH);
+    let salt = &header[18..18 + SALT_LENGTH];
+    let nonce = &header[18 + SALT_LENGTH..];
+
+    let key = params.derive(passphrase.as_bytes(), salt)?;
+    let plaintext = cipher
+        .open(
+            &key,
+            nonce,
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .ok_or_else(|| error("Wrong passphrase, or the message was changed"))?;
+
+    Ok((plaintext, Decrypted { cipher, params }))
+}
+
+/// Base64 between BEGIN and END lines, so the message survives being copied around
+pub fn armor(message: &[u8]) -> String {
+    let encoded = BASE64.encode(message);
+    let lines: Vec<&str> = encoded
+        .as_bytes()
+        .chunks(ARMOR_WIDTH)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect();
+
+    format!("{}\n{}\n{}", ARMOR_BEGIN, lines.join("\n"), ARMOR_END)
+}
+
+/// Read an armored message, with or without the BEGIN and END lines and line breaks
+pub fn dearmor(text: &str) -> Result<Vec<u8>, CryptoError> {
+    let text = text.trim();
+    let text = text.strip_prefix(ARMOR_BEGIN).unwrap_or(text);
+    let text = text.strip_suffix(ARMOR_END).unwrap_or(text);
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+    BASE64
+        .decode(compact.as_bytes())
+        .map_err(|e| error(format!("Invalid armored message, {}", e)))
+}
+
+pub fn generate_signing_key(rng: &mut (impl Rng + CryptoRng)) -> SigningKey {
+    SigningKey::generate(rng)
+}
+
+/// Keys and signatures are written in base64
+pub fn encode_key(bytes: &[u8]) -> String {
+    BASE64.encode(bytes)
+}
+
+fn decode_exact<const N: usize>(text: &str, what: &str) -> Result<[u8; N], CryptoError> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let bytes = BASE64
+        .decode(compact.as_bytes())
+        .map_err(|e| error(format!("Invalid {}, {}", what, e)))?;
+
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        error(format!(
+            "Invalid {}, expected {} bytes but got {}",
+            what,
+            N,
+            bytes.len()
+        ))
+    })
+}
+
+pub fn parse_signing_key(text: &str) -> Result<SigningKey, CryptoError> {
+    Ok(SigningKey::from_bytes(&decode_exact(text, "private key")?))
+}
+
+pub fn parse_verifying_key(text: &str) -> Result<VerifyingKey, CryptoError> {
+    VerifyingKey::from_bytes(&decode_exact(text, "public key")?)
+        .map_err(|_| error("Invalid public key, it is not a point on the curve"))
+}
+
+pub fn sign(key: &SigningKey, message: &[u8]) -> [u8; 64] {
+    key.sign(message).to_bytes()
+}
+
+/// Whether `signature` was made for `message` by the private half of `key`
+pub fn verify(key: &VerifyingKey, message: &[u8], signature: &str) -> Result<bool, CryptoError> {
+    let signature = Signature::from_bytes(&decode_exact(signature, "signature")?);
+
+    Ok(key.verify(message, &signature).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    /// Cheap parameters so the tests do not spend time deriving keys
+    const PARAMS: KeyParams = KeyParams {
+        memory: 64,
+        time: 1,
+        parallelism: 1,
+    };
+
+    fn encrypted(cipher: Cipher) -> Vec<u8> {
+        encrypt(&mut OsRng, b"attack at dawn", "hunter2", cipher, PARAMS).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        for cipher in [Cipher::ChaCha20Poly1305, Cipher::Aes256Gcm] {
+            let message = encrypted(cipher);
+            let armored = armor(&message);
+            let (plaintext, decrypted) = decrypt(&dearmor(&armored).unwrap(), "hunter2").unwrap();
+
+            assert_eq!(plaintext, b"attack at dawn");
+            assert_eq!(decrypted.cipher, cipher);
+            assert_eq!(decrypted.params, PARAMS);
+        }
+    }
+
+    #[test]
+    fn wrong_passphrase() {
+        let message = encrypted(Cipher::ChaCha20Poly1305);
+        assert!(decrypt(&message, "hunter3").is_err());
+    }
+
+    #[test]
+    fn truncated_message() {
+        let message = encrypted(Cipher::Aes256Gcm);
+        for length in [0, 4, HEADER_LENGTH - 1, HEADER_LENGTH, message.len() - 1] {
+            assert!(
+                decrypt(&message[..length], "hunter2").is_err(),
+                "{}",
+                length
+            );
+        }
+    }
+
+    #[test]
+    fn tampered_message() {
+        let message = encrypted(Cipher::ChaCha20Poly1305);
+        // 헤더의 모든 바이트와 암호문의 바이트를 하나씩 바꿔봄
+        for index in 0..message.len() {
+            let mut tampered = message.clone();
+            tampered[index] ^= 0x01;
+            assert!(decrypt(&tampered, "hunter2").is_err(), "byte {}", index);
+        }
+    }
+
+    #[test]
+    fn costly_parameters() {
+        let message = encrypted(Cipher::ChaCha20Poly1305);
+        for (offset, value) in [
+            (6, MAX_MEMORY + 1),
+            (10, MAX_TIME + 1),
+            (14, MAX_PARALLELISM + 1),
+        ] {
+            let mut crafted = message.clone();
+            crafted[offset..offset + 4].copy_from_slice(&value.to_be_bytes());
+            let error = decrypt(&crafted, "hunter2").unwrap_err();
+            assert!(error.to_string().contains("too costly"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let key = generate_signing_key(&mut OsRng);
+        let public = parse_verifying_key(&encode_key(key.verifying_key().as_bytes())).unwrap();
+        let signature = sign(&key, b"hello");
+        let encoded = encode_key(&signature);
+
+        assert!(verify(&public, b"hello", &encoded).unwrap());
+        assert!(!verify(&public, b"hellp", &encoded).unwrap());
+
+        let mut flipped = signature;
+        flipped[10] ^= 0x01;
+        assert!(!verify(&public, b"hello", &encode_key(&flipped)).unwrap());
+
+        let other = generate_signing_key(&mut OsRng).verifying_key();
+        assert!(!verify(&other, b"hello", &encoded).unwrap());
+    }
+
+    #[test]
+    fn signing_key_round_trip() {
+        let key = generate_signing_key(&mut OsRng);
+        let parsed = parse_signing_key(&encode_key(key.as_bytes())).unwrap();
+        assert_eq!(parsed.as_bytes(), key.as_bytes());
+        assert!(parse_signing_key("AAAA").is_err());
+    }
+}
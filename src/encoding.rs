@@ -0,0 +1,439 @@
+//! Text encodings of bytes and of text, and guessing which one a string is in
+
+use bip39::Language;
+use data_encoding::{BASE32_NOPAD, BASE64URL_NOPAD, BASE64_NOPAD, HEXLOWER, HEXLOWER_PERMISSIVE};
+use std::fmt;
+
+#[derive(Debug)]
+pub struct EncodingError(String);
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+fn error(message: impl Into<String>) -> EncodingError {
+    EncodingError(message.into())
+}
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Characters kept as they are in percent-encoding, the unreserved ones of RFC 3986
+const UNRESERVED: &str = "-._~";
+
+/// Named HTML entities that are decoded, numeric ones are always decoded
+const ENTITIES: [(&str, char); 16] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("copy", '©'),
+    ("reg", '®'),
+    ("trade", '™'),
+    ("hellip", '…'),
+    ("mdash", '—'),
+    ("ndash", '–'),
+    ("laquo", '«'),
+    ("raquo", '»'),
+    ("euro", '€'),
+    ("middot", '·'),
+];
+
+/// International Morse code for letters, digits and common punctuation
+const MORSE: [(char, &str); 54] = [
+    ('a', ".-"),
+    ('b', "-..."),
+    ('c', "-.-."),
+    ('d', "-.."),
+    ('e', "."),
+    ('f', "..-."),
+    ('g', "--."),
+    ('h', "...."),
+    ('i', ".."),
+    ('j', ".---"),
+    ('k', "-.-"),
+    ('l', ".-.."),
+    ('m', "--"),
+    ('n', "-."),
+    ('o', "---"),
+    ('p', ".--."),
+    ('q', "--.-"),
+    ('r', ".-."),
+    ('s', "..."),
+    ('t', "-"),
+    ('u', "..-"),
+    ('v', "...-"),
+    ('w', ".--"),
+    ('x', "-..-"),
+    ('y', "-.--"),
+    ('z', "--.."),
+    ('0', "-----"),
+    ('1', ".----"),
+    ('2', "..---"),
+    ('3', "...--"),
+    ('4', "....-"),
+    ('5', "....."),
+    ('6', "-...."),
+    ('7', "--..."),
+    ('8', "---.."),
+    ('9', "----."),
+    ('.', ".-.-.-"),
+    (',', "--..--"),
+    ('?', "..--.."),
+    ('\'', ".----."),
+    ('!', "-.-.--"),
+    ('/', "-..-."),
+    ('(', "-.--."),
+    (')', "-.--.-"),
+    ('&', ".-..."),
+    (':', "---..."),
+    (';', "-.-.-."),
+    ('=', "-...-"),
+    ('+', ".-.-."),
+    ('-', "-....-"),
+    ('_', "..--.-"),
+    ('"', ".-..-."),
+    ('$', "...-..-"),
+    ('@', ".--.-."),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    Base64,
+    /// Base64 with `-` and `_` instead of `+` and `/`, and no padding
+    Base64Url,
+    Base32,
+    /// Bitcoin's alphabet, without `0`, `O`, `I` and `l`
+    Base58,
+    Hex,
+    /// URL percent-encoding of the UTF-8 bytes
+    Percent,
+    Html,
+    Rot13,
+    /// Letters separated by spaces and words by ` / `
+    Morse,
+}
+
+impl Codec {
+    pub const ALL: [Codec; 9] = [
+        Codec::Base64,
+        Codec::Base64Url,
+        Codec::Base32,
+        Codec::Base58,
+        Codec::Hex,
+        Codec::Percent,
+        Codec::Html,
+        Codec::Rot13,
+        Codec::Morse,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Codec::Base64 => "base64",
+            Codec::Base64Url => "base64url",
+            Codec::Base32 => "base32",
+            Codec::Base58 => "base58",
+            Codec::Hex => "hex",
+            Codec::Percent => "URL percent-encoding",
+            Codec::Html => "HTML entities",
+            Codec::Rot13 => "ROT13",
+            Codec::Morse => "Morse code",
+        }
+    }
+
+    pub fn encode(self, input: &str) -> Result<String, EncodingError> {
+        let bytes = input.as_bytes();
+        Ok(match self {
+            Codec::Base64 => data_encoding::BASE64.encode(bytes),
+            Codec::Base64Url => BASE64URL_NOPAD.encode(bytes),
+            Codec::Base32 => data_encoding::BASE32.encode(bytes),
+            Codec::Base58 => base58_encode(bytes),
+            Codec::Hex => HEXLOWER.encode(bytes),
+            Codec::Percent => bytes
+                .iter()
+                .map(|&byte| {
+                    if byte.is_ascii_alphanumeric() || UNRESERVED.contains(byte as char) {
+                        (byte as char).to_string()
+                    } else {
+                        format!("%{:02X}", byte)
+                    }
+                })
+                .collect(),
+            Codec::Html => input
+                .chars()
+                .map(|c| match c {
+                    '&' => "&amp;".into(),
+                    '<' => "&lt;".into(),
+                    '>' => "&gt;".into(),
+                    '"' => "&quot;".into(),
+                    '\'' => "&#39;".into(),
+                    c => c.to_string(),
+                })
+                .collect(),
+            Codec::Rot13 => rot13(input),
+            Codec::Morse => morse_encode(input)?,
+        })
+    }
+
+    /// Decode to bytes, which are text for every codec except the base and hex ones
+    ///
+    /// Whitespace inside base and hex input is ignored, and so is missing padding.
+    pub fn decode(self, input: &str) -> Result<Vec<u8>, EncodingError> {
+        let compact: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+        let invalid =
+            |e: data_encoding::DecodeError| error(format!("Invalid {}, {}", self.name(), e));
+
+        match self {
+            Codec::Base64 => BASE64_NOPAD
+                .decode(compact.trim_end_matches('=').as_bytes())
+                .map_err(invalid),
+            Codec::Base64Url => BASE64URL_NOPAD
+                .decode(compact.trim_end_matches('=').as_bytes())
+                .map_err(invalid),
+            Codec::Base32 => BASE32_NOPAD
+                .decode(compact.trim_end_matches('=').to_uppercase().as_bytes())
+                .map_err(invalid),
+            Codec::Base58 => base58_decode(&compact),
+            Codec::Hex => HEXLOWER_PERMISSIVE
+                .decode(compact.as_bytes())
+                .map_err(invalid),
+            Codec::Percent => percent_decode(input),
+            Codec::Html => Ok(html_decode(input).into_bytes()),
+            Codec::Rot13 => Ok(rot13(input).into_bytes()),
+            Codec::Morse => morse_decode(input).map(String::into_bytes),
+        }
+    }
+}
+
+fn base58_encode(bytes: &[u8]) -> String {
+    // 큰 정수를 58진법 자릿수로 나눔, 자릿수는 낮은 자리부터 저장
+    let mut digits: Vec<u8> = vec![];
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in &mut digits {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // 앞쪽의 0 바이트는 각각 `1`이 됨
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    std::iter::repeat_n('1', zeros)
+        .chain(
+            digits
+                .iter()
+                .rev()
+                .map(|&digit| BASE58_ALPHABET[digit as usize] as char),
+        )
+        .collect()
+}
+
+fn base58_decode(input: &str) -> Result<Vec<u8>, EncodingError> {
+    let mut bytes: Vec<u8> = vec![];
+    for c in input.chars() {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|&letter| letter as char == c)
+            .ok_or_else(|| error(format!("Invalid base58, `{}` is not in the alphabet", c)))?
+            as u32;
+        for byte in &mut bytes {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let zeros = input.chars().take_while(|&c| c == '1').count();
+    bytes.extend(std::iter::repeat_n(0, zeros));
+    bytes.reverse();
+
+    Ok(bytes)
+}
+
+fn percent_decode(input: &str) -> Result<Vec<u8>, EncodingError> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let byte = bytes
+                .get(index + 1..index + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| {
+                    error("Invalid URL percent-encoding, `%` must be followed by two hex digits")
+                })?;
+            decoded.push(byte);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    Ok(decoded)
+}
+
+/// Replace entities like `&amp;`, `&#39;` and `&#x1F600;`, leaving unknown ones as they are
+fn html_decode(input: &str) -> String {
+    let mut decoded = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let c = entity.and_then(|entity| match entity.strip_prefix('#') {
+            Some(number) => match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => number.parse().ok(),
+            }
+            .and_then(char::from_u32),
+            None => ENTITIES
+                .iter()
+                .find(|(name, _)| *name == entity)
+                .map(|&(_, c)| c),
+        });
+
+        match (entity, c) {
+            (Some(entity), Some(c)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+fn rot13(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            'a'..='z' => ((c as u8 - b'a' + 13) % 26 + b'a') as char,
+            'A'..='Z' => ((c as u8 - b'A' + 13) % 26 + b'A') as char,
+            c => c,
+        })
+        .collect()
+}
+
+fn morse_encode(input: &str) -> Result<String, EncodingError> {
+    let words = input.split_whitespace().map(|word| {
+        word.chars()
+            .map(|c| {
+                MORSE
+                    .iter()
+                    .find(|(letter, _)| *letter == c.to_ascii_lowercase())
+                    .map(|(_, code)| *code)
+                    .ok_or_else(|| error(format!("`{}` has no Morse code", c)))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|codes| codes.join(" "))
+    });
+
+    Ok(words.collect::<Result<Vec<_>, _>>()?.join(" / "))
+}
+
+fn morse_decode(input: &str) -> Result<String, EncodingError> {
+    // 여러 가지 점과 선 기호를 받아들임
+    let input: String = input
+        .chars()
+        .map(|c| match c {
+            '·' | '•' => '.',
+            '−' | '–' | '—' | '_' => '-',
+            c => c,
+        })
+        .collect();
+
+    let words = input.split(['/', '|']).map(|word| {
+        word.split_whitespace()
+            .map(|code| {
+                MORSE
+                    .iter()
+                    .find(|(_, known)| *known == code)
+                    .map(|&(letter, _)| letter)
+                    .ok_or_else(|| error(format!("Invalid Morse code, `{}` is not a letter", code)))
+            })
+            .collect::<Result<String, _>>()
+    });
+
+    let words = words.collect::<Result<Vec<_>, _>>()?;
+    Ok(words.join(" ").trim().to_string())
+}
+
+/// Whether decoded bytes look like text someone would have encoded
+fn is_readable(bytes: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    let readable =
+        !text.trim().is_empty() && text.chars().all(|c| !c.is_control() || c.is_whitespace());
+    readable.then(|| text.to_string())
+}
+
+/// How many of the words in the text are English words
+fn english_words(text: &str) -> usize {
+    text.split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| word.len() >= 3)
+        .filter(|word| Language::English.find_word(&word.to_lowercase()).is_some())
+        .count()
+}
+
+/// Decode the input with every codec and keep the results that look like text
+///
+/// ROT13 always succeeds, so it is only kept when it turns up more English words than
+/// the input had. A result already given by an earlier codec is not repeated.
+pub fn detect(input: &str) -> Vec<(Codec, String)> {
+    let input = input.trim();
+    let mut found: Vec<(Codec, String)> = Vec::new();
+
+    let decoded = Codec::ALL.into_iter().filter_map(|codec| {
+        let plausible = match codec {
+            // 짧은 숫자나 단어도 여러 진법으로 읽힐 수 있으므로 형식이 분명한 것만 봄
+            Codec::Hex => input.len().is_multiple_of(2) && input.len() >= 4,
+            Codec::Base32 | Codec::Base58 | Codec::Base64 | Codec::Base64Url => input.len() >= 4,
+            Codec::Percent => input.contains('%'),
+            Codec::Html => input.contains('&') && input.contains(';'),
+            Codec::Morse => input.chars().all(|c| ".-·−–—_/| ".contains(c)),
+            Codec::Rot13 => input.chars().any(|c| c.is_ascii_alphabetic()),
+        };
+        if !plausible {
+            return None;
+        }
+
+        let decoded = is_readable(&codec.decode(input).ok()?)?;
+        let changed = match codec {
+            Codec::Rot13 => english_words(&decoded) > english_words(input),
+            _ => decoded != input,
+        };
+        changed.then_some((codec, decoded))
+    });
+
+    for (codec, text) in decoded {
+        if found.iter().all(|(_, seen)| *seen != text) {
+            found.push((codec, text));
+        }
+    }
+
+    found
+}
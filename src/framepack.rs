@@ -0,0 +1,273 @@
+//! Single file holding every text frame of an animation
+//!
+//! All numbers are little endian:
+//!
+//! | field        | size                                              |
+//! |--------------|---------------------------------------------------|
+//! | magic        | `ARCF`                                            |
+//! | version      | u8                                                |
+//! | width        | u16, characters per line                          |
+//! | height       | u16, lines per frame                              |
+//! | fps          | f32                                               |
+//! | frame count  | u32                                               |
+//! | charset      | u16 byte length, then UTF-8, darkest cell first   |
+//! | offsets      | frame count + 1 u32, frame bounds in the data     |
+//! | data         | every frame as zstd-compressed cell indices       |
+//!
+//! Frames are compressed one by one so any frame can be decoded without the others.
+
+use crate::braille;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"ARCF";
+const VERSION: u8 = 1;
+const COMPRESSION_LEVEL: i32 = 19;
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Frames of an animation kept compressed in memory
+pub struct FramePack {
+    width: u16,
+    height: u16,
+    fps: f32,
+    charset: Vec<char>,
+    offsets: Vec<u32>,
+    data: Vec<u8>,
+}
+
+impl FramePack {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = bytes;
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a frame pack"));
+        }
+        if read_u8(&mut reader)? != VERSION {
+            return Err(invalid_data("unsupported frame pack version"));
+        }
+
+        let width = read_u16(&mut reader)?;
+        let height = read_u16(&mut reader)?;
+        let fps = f32::from_bits(read_u32(&mut reader)?);
+        // 재생 위치를 fps로 계산하므로 0이나 음수, NaN이면 첫 프레임에 멈춤
+        if !fps.is_finite() || fps <= 0.0 {
+            return Err(invalid_data("the frame rate must be a positive number"));
+        }
+        let frame_count = read_u32(&mut reader)? as usize;
+
+        let mut charset = vec![0; read_u16(&mut reader)? as usize];
+        reader.read_exact(&mut charset)?;
+        let charset: Vec<char> = String::from_utf8(charset)
+            .map_err(|_| invalid_data("the charset is not UTF-8"))?
+            .chars()
+            .collect();
+        if charset.is_empty() || charset.len() > 256 {
+            return Err(invalid_data("the charset must have 1 to 256 characters"));
+        }
+
+        // 잘못된 헤더로 큰 메모리를 잡지 않도록 오프셋이 실제로 들어 있는지 먼저 확인함
+        if (frame_count + 1) * 4 > reader.len() {
+            return Err(invalid_data("the frame count doesn't fit in the pack"));
+        }
+
+        let mut offsets = Vec::with_capacity(frame_count + 1);
+        for _ in 0..=frame_count {
+            offsets.push(read_u32(&mut reader)?);
+        }
+        let data = reader.to_vec();
+
+        if offsets.windows(2).any(|bounds| bounds[0] > bounds[1])
+            || offsets.last().is_some_and(|&end| end as usize > data.len())
+        {
+            return Err(invalid_data("the frame offsets are out of bounds"));
+        }
+
+        Ok(FramePack {
+            width,
+            height,
+            fps,
+            charset,
+            offsets,
+            data,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn fps(&self) -> f32 {
+        self.fps
+    }
+
+    /// Decompress a single frame into text
+    pub fn frame(&self, index: usize) -> io::Result<String> {
+        if index >= self.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("frame {} is out of range", index),
+            ));
+        }
+
+        let start = self.offsets[index] as usize;
+        let end = self.offsets[index + 1] as usize;
+        let cells = zstd::decode_all(&self.data[start..end])?;
+
+        if cells.len() != self.width as usize * self.height as usize
+            || cells
+                .iter()
+                .any(|&cell| cell as usize >= self.charset.len())
+        {
+            return Err(invalid_data("the frame doesn't match the header"));
+        }
+
+        Ok(braille::to_text(&cells, self.width as u32, &self.charset))
+    }
+}
+
+/// Builds a frame pack one frame at a time
+pub struct FramePackWriter {
+    width: u16,
+    height: u16,
+    fps: f32,
+    charset: String,
+    offsets: Vec<u32>,
+    data: Vec<u8>,
+}
+
+impl FramePackWriter {
+    pub fn new(width: u16, height: u16, fps: f32, charset: &[char]) -> Self {
+        FramePackWriter {
+            width,
+            height,
+            fps,
+            charset: charset.iter().collect(),
+            offsets: vec![0],
+            data: vec![],
+        }
+    }
+
+    /// Add a frame made of `width * height` cell indices
+    pub fn push(&mut self, cells: &[u8]) -> io::Result<()> {
+        if cells.len() != self.width as usize * self.height as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "every frame must have the same size",
+            ));
+        }
+
+        self.data
+            .extend(zstd::encode_all(cells, COMPRESSION_LEVEL)?);
+        self.offsets.push(self.data.len() as u32);
+
+        Ok(())
+    }
+
+    /// Change the frame rate, for sources whose rate is only known once every frame is read
+    pub fn set_fps(&mut self, fps: f32) {
+        self.fps = fps;
+    }
+
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&self.width.to_le_bytes())?;
+        writer.write_all(&self.height.to_le_bytes())?;
+        writer.write_all(&self.fps.to_le_bytes())?;
+        writer.write_all(&(self.offsets.len() as u32 - 1).to_le_bytes())?;
+        writer.write_all(&(self.charset.len() as u16).to_le_bytes())?;
+        writer.write_all(self.charset.as_bytes())?;
+        for offset in &self.offsets {
+            writer.write_all(&offset.to_le_bytes())?;
+        }
+        writer.write_all(&self.data)?;
+
+        Ok(())
+    }
+}
+
+fn read_u8(reader: &mut impl Read) -> io::Result<u8> {
+    let mut bytes = [0; 1];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes[0])
+}
+
+fn read_u16(reader: &mut impl Read) -> io::Result<u16> {
+    let mut bytes = [0; 2];
+    reader.read_exact(&mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packed(fps: f32) -> Vec<u8> {
+        let mut writer = FramePackWriter::new(2, 1, fps, &[' ', '#']);
+        writer.push(&[0, 1]).unwrap();
+        writer.push(&[1, 0]).unwrap();
+
+        let mut bytes = vec![];
+        writer.write_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn round_trip() {
+        let pack = FramePack::from_bytes(&packed(24.0)).unwrap();
+
+        assert_eq!(pack.len(), 2);
+        assert_eq!(pack.fps(), 24.0);
+        assert_eq!(
+            pack.frame(0).unwrap(),
+            braille::to_text(&[0, 1], 2, &[' ', '#'])
+        );
+        assert!(pack.frame(2).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_frame_rates() {
+        for fps in [0.0, -30.0, f32::NAN, f32::INFINITY] {
+            assert!(FramePack::from_bytes(&packed(fps)).is_err(), "{}", fps);
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_packs() {
+        let bytes = packed(24.0);
+        for length in 0..bytes.len() {
+            assert!(
+                FramePack::from_bytes(&bytes[..length]).is_err(),
+                "{}",
+                length
+            );
+        }
+    }
+}
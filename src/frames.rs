@@ -0,0 +1,190 @@
+use image::{codecs::gif::GifDecoder, AnimationDecoder, DynamicImage, ImageResult};
+use std::io::{self, Cursor};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A decoded frame and how long it stays on screen
+pub struct Frame {
+    pub image: DynamicImage,
+    pub delay: Duration,
+}
+
+/// Delay used for GIF frames that don't specify one, like browsers do
+const DEFAULT_GIF_DELAY: Duration = Duration::from_millis(100);
+
+/// Decode the frames of an animated GIF one at a time
+pub fn gif_frames(data: &[u8]) -> ImageResult<impl Iterator<Item = ImageResult<Frame>> + '_> {
+    let decoder = GifDecoder::new(Cursor::new(data))?;
+
+    Ok(decoder.into_frames().map(|frame| {
+        let frame = frame?;
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        let delay = Duration::from_micros(numer as u64 * 1000 / denom.max(1) as u64);
+        let delay = if delay <= Duration::from_millis(10) {
+            DEFAULT_GIF_DELAY
+        } else {
+            delay
+        };
+
+        Ok(Frame {
+            image: DynamicImage::ImageRgba8(frame.into_buffer()),
+            delay,
+        })
+    }))
+}
+
+/// Load every image of a directory as a frame, ordered by the number in the file name
+/// (`frame2.jpg` comes before `frame10.jpg`)
+pub fn dir_frames(
+    dir: &Path,
+    delay: Duration,
+) -> io::Result<impl Iterator<Item = ImageResult<Frame>>> {
+    let mut paths = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && image::ImageFormat::from_path(&path).is_ok() {
+            paths.push(path);
+        }
+    }
+
+    paths.sort_by_cached_key(|path| {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let number: String = name.chars().filter(|c| c.is_ascii_digit()).collect();
+        (number.parse::<u64>().ok(), name)
+    });
+
+    Ok(paths.into_iter().map(move |path| {
+        Ok(Frame {
+            image: image::open(path)?,
+            delay,
+        })
+    }))
+}
+
+/// Frames extracted from a video by the `ffmpeg` executable,
+/// stored in a temporary directory that is removed when this is dropped
+pub struct VideoFrames {
+    dir: PathBuf,
+    next: usize,
+    delay: Duration,
+}
+
+impl VideoFrames {
+    /// Extract the frames of a video file, sampling `fps` frames per second
+    /// (or every frame when `None`) scaled down to `scale_width` pixels wide,
+    /// stopping after `max_frames` frames when given
+    pub fn from_path(
+        input: &Path,
+        fps: Option<u32>,
+        scale_width: u32,
+        max_frames: Option<usize>,
+    ) -> io::Result<Self> {
+        let frames = Self::new(fps)?;
+        frames.extract(input, fps, scale_width, max_frames)?;
+        Ok(frames)
+    }
+
+    /// Same as [`VideoFrames::from_path`] for a video held in memory
+    pub fn from_bytes(
+        data: &[u8],
+        fps: Option<u32>,
+        scale_width: u32,
+        max_frames: Option<usize>,
+    ) -> io::Result<Self> {
+        let frames = Self::new(fps)?;
+        let input = frames.dir.join("input");
+        std::fs::write(&input, data)?;
+        frames.extract(&input, fps, scale_width, max_frames)?;
+        Ok(frames)
+    }
+
+    fn new(fps: Option<u32>) -> io::Result<Self> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("arcum-{}-{}", std::process::id(), nanos));
+        std::fs::create_dir_all(&dir)?;
+
+        Ok(VideoFrames {
+            dir,
+            next: 1,
+            // Without resampling the real frame rate is unknown, assume the usual 30fps
+            delay: Duration::from_secs(1) / fps.unwrap_or(30).max(1),
+        })
+    }
+
+    fn extract(
+        &self,
+        input: &Path,
+        fps: Option<u32>,
+        scale_width: u32,
+        max_frames: Option<usize>,
+    ) -> io::Result<()> {
+        let filter = match fps {
+            Some(fps) => format!("fps={},scale={}:-2", fps, scale_width),
+            None => format!("scale={}:-2", scale_width),
+        };
+
+        let mut command = Command::new("ffmpeg");
+        command
+            .arg("-loglevel")
+            .arg("error")
+            .arg("-i")
+            .arg(input)
+            .arg("-vf")
+            .arg(filter);
+        if let Some(max_frames) = max_frames {
+            command.arg("-frames:v").arg(max_frames.to_string());
+        }
+
+        let output = command
+            .arg(self.dir.join("frame%06d.png"))
+            .output()
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => {
+                    io::Error::new(e.kind(), "ffmpeg is required to decode videos")
+                }
+                _ => e,
+            })?;
+
+        if !output.status.success() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Iterator for VideoFrames {
+    type Item = ImageResult<Frame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let path = self.dir.join(format!("frame{:06}.png", self.next));
+        if !path.exists() {
+            return None;
+        }
+        self.next += 1;
+
+        let frame = image::open(&path).map(|image| Frame {
+            image,
+            delay: self.delay,
+        });
+        std::fs::remove_file(path).ok();
+
+        Some(frame)
+    }
+}
+
+impl Drop for VideoFrames {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.dir).ok();
+    }
+}
@@ -0,0 +1,175 @@
+//! Color stops and interpolation between them
+
+use crate::color::{from_linear, to_linear, Color, ParseColorError};
+use std::str::FromStr;
+
+/// Color space in which neighbouring stops are blended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Space {
+    /// Gamma encoded channels, which turns dark and muddy between complementary colors
+    Srgb,
+    LinearRgb,
+    Oklab,
+    /// Hue takes the shorter way around the color wheel
+    Hsl,
+}
+
+impl Space {
+    pub fn mix(self, from: Color, to: Color, t: f64) -> Color {
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+        let alpha = lerp(from.a as f64, to.a as f64).round() as u8;
+
+        let color = match self {
+            Space::Srgb => {
+                let (from, to) = (from.to_unit(), to.to_unit());
+                Color::from_unit(lerp(from.0, to.0), lerp(from.1, to.1), lerp(from.2, to.2))
+            }
+            Space::LinearRgb => {
+                let (from, to) = (from.to_unit(), to.to_unit());
+                let channel = |a: f64, b: f64| from_linear(lerp(to_linear(a), to_linear(b)));
+                Color::from_unit(
+                    channel(from.0, to.0),
+                    channel(from.1, to.1),
+                    channel(from.2, to.2),
+                )
+            }
+            Space::Oklab => {
+                let (from, to) = (from.to_oklab(), to.to_oklab());
+                Color::from_oklab(lerp(from.0, to.0), lerp(from.1, to.1), lerp(from.2, to.2))
+            }
+            Space::Hsl => {
+                let (mut h1, s1, l1) = from.to_hsl();
+                let (mut h2, s2, l2) = to.to_hsl();
+                // 회색에는 색상이 없으므로 반대쪽 색상을 그대로 사용
+                if s1 == 0.0 {
+                    h1 = h2;
+                } else if s2 == 0.0 {
+                    h2 = h1;
+                }
+                let mut delta = (h2 - h1).rem_euclid(360.0);
+                if delta > 180.0 {
+                    delta -= 360.0;
+                }
+                Color::from_hsl(h1 + delta * t, lerp(s1, s2), lerp(l1, l2))
+            }
+        };
+
+        Color { a: alpha, ..color }
+    }
+}
+
+/// Colors at positions from 0 to 1, in increasing order
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    stops: Vec<(f64, Color)>,
+}
+
+impl Gradient {
+    pub fn stops(&self) -> &[(f64, Color)] {
+        &self.stops
+    }
+
+    /// The color at `t`, clamped to the first and last stops
+    pub fn sample(&self, t: f64, space: Space) -> Color {
+        let stops = &self.stops;
+        let next = stops.partition_point(|&(position, _)| position <= t);
+
+        if next == 0 {
+            return stops[0].1;
+        }
+        if next == stops.len() {
+            return stops[next - 1].1;
+        }
+
+        let (start, from) = stops[next - 1];
+        let (end, to) = stops[next];
+        if end <= start {
+            return to;
+        }
+        space.mix(from, to, (t - start) / (end - start))
+    }
+}
+
+/// Split at commas and semicolons that are not inside parentheses
+fn split_stops(input: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' | ';' if depth <= 0 => {
+                parts.push(&input[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+impl FromStr for Gradient {
+    type Err = ParseColorError;
+
+    /// CSS-like stops such as `red, rgb(0 0 255) 30%, #fff`
+    ///
+    /// Stops without a position are spread evenly between their neighbours,
+    /// and a position is never less than the one before it.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut stops: Vec<(Option<f64>, Color)> = vec![];
+
+        for part in split_stops(input) {
+            let position = part
+                .rsplit_once(char::is_whitespace)
+                .and_then(|(color, last)| {
+                    let percent = last.strip_suffix('%')?.parse::<f64>().ok()?;
+                    Some((color, percent / 100.0))
+                });
+
+            stops.push(match position {
+                Some((color, position)) if position.is_finite() => {
+                    (Some(position), Color::parse(color)?)
+                }
+                _ => (None, Color::parse(part)?),
+            });
+        }
+
+        if stops.is_empty() {
+            return Err(ParseColorError::new("there are no color stops"));
+        }
+
+        let last = stops.len() - 1;
+        stops[0].0.get_or_insert(0.0);
+        stops[last].0.get_or_insert(1.0);
+
+        // 위치가 없는 구간은 양쪽의 위치 사이를 고르게 나눔
+        let mut known = 0;
+        for index in 1..stops.len() {
+            if let Some(position) = stops[index].0 {
+                let start = stops[known].0.unwrap();
+                let position = position.max(start);
+                let gap = (index - known) as f64;
+                for (step, stop) in stops[known + 1..index].iter_mut().enumerate() {
+                    stop.0 = Some(start + (position - start) * (step + 1) as f64 / gap);
+                }
+                stops[index].0 = Some(position);
+                known = index;
+            }
+        }
+
+        Ok(Gradient {
+            stops: stops
+                .into_iter()
+                .map(|(position, color)| (position.unwrap(), color))
+                .collect(),
+        })
+    }
+}
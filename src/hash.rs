@@ -0,0 +1,322 @@
+//! Hash functions and the text encodings their digests are shown in
+
+use data_encoding::{BASE32, BASE64, HEXLOWER};
+use hmac::{Hmac, Mac};
+use sha2::Digest;
+use xxhash_rust::{xxh3::Xxh3, xxh64::Xxh64};
+
+/// Hash function, cryptographic or not
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha3_256,
+    Sha3_512,
+    Blake2b,
+    Blake3,
+    /// The checksum used by zip, PNG and Ethernet, not meant to resist tampering
+    Crc32,
+    /// Fast non-cryptographic hashes with a seed of 0
+    Xxh64,
+    Xxh3,
+}
+
+impl Algorithm {
+    pub fn hasher(self) -> Hasher {
+        match self {
+            Algorithm::Md5 => Hasher::Md5(md5::Md5::new()),
+            Algorithm::Sha1 => Hasher::Sha1(sha1::Sha1::new()),
+            Algorithm::Sha224 => Hasher::Sha224(sha2::Sha224::new()),
+            Algorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+            Algorithm::Sha384 => Hasher::Sha384(sha2::Sha384::new()),
+            Algorithm::Sha512 => Hasher::Sha512(sha2::Sha512::new()),
+            Algorithm::Sha3_256 => Hasher::Sha3_256(sha3::Sha3_256::new()),
+            Algorithm::Sha3_512 => Hasher::Sha3_512(sha3::Sha3_512::new()),
+            Algorithm::Blake2b => Hasher::Blake2b(blake2::Blake2b512::new()),
+            Algorithm::Blake3 => Hasher::Blake3(Box::default()),
+            Algorithm::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+            Algorithm::Xxh64 => Hasher::Xxh64(Xxh64::new(0)),
+            Algorithm::Xxh3 => Hasher::Xxh3(Box::default()),
+        }
+    }
+
+    /// The digest of `input`, with integer results in big-endian like they are usually written
+    pub fn digest(self, input: &[u8]) -> Vec<u8> {
+        let mut hasher = self.hasher();
+        hasher.update(input);
+        hasher.finalize()
+    }
+}
+
+/// The state of an [`Algorithm`] fed with data a piece at a time
+#[derive(Clone)]
+pub enum Hasher {
+    Md5(md5::Md5),
+    Sha1(sha1::Sha1),
+    Sha224(sha2::Sha224),
+    Sha256(sha2::Sha256),
+    Sha384(sha2::Sha384),
+    Sha512(sha2::Sha512),
+    Sha3_256(sha3::Sha3_256),
+    Sha3_512(sha3::Sha3_512),
+    Blake2b(blake2::Blake2b512),
+    Blake3(Box<blake3::Hasher>),
+    Crc32(crc32fast::Hasher),
+    Xxh64(Xxh64),
+    Xxh3(Box<Xxh3>),
+}
+
+impl Hasher {
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(hasher) => hasher.update(data),
+            Hasher::Sha1(hasher) => hasher.update(data),
+            Hasher::Sha224(hasher) => hasher.update(data),
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Sha384(hasher) => hasher.update(data),
+            Hasher::Sha512(hasher) => hasher.update(data),
+            Hasher::Sha3_256(hasher) => hasher.update(data),
+            Hasher::Sha3_512(hasher) => hasher.update(data),
+            Hasher::Blake2b(hasher) => hasher.update(data),
+            Hasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+            Hasher::Crc32(hasher) => hasher.update(data),
+            Hasher::Xxh64(hasher) => hasher.update(data),
+            Hasher::Xxh3(hasher) => hasher.update(data),
+        }
+    }
+
+    /// The digest, with integer results in big-endian like they are usually written
+    pub fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Md5(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha1(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha224(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha384(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha512(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha3_256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha3_512(hasher) => hasher.finalize().to_vec(),
+            Hasher::Blake2b(hasher) => hasher.finalize().to_vec(),
+            Hasher::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+            Hasher::Crc32(hasher) => hasher.finalize().to_be_bytes().to_vec(),
+            Hasher::Xxh64(hasher) => hasher.digest().to_be_bytes().to_vec(),
+            Hasher::Xxh3(hasher) => hasher.digest().to_be_bytes().to_vec(),
+        }
+    }
+}
+
+/// Whether `expected` is the digest written in any of the [`Encoding`]s
+///
+/// Surrounding whitespace and padding are ignored, and so is case except in base64.
+pub fn matches(digest: &[u8], expected: &str) -> bool {
+    let expected = expected.trim().trim_end_matches('=');
+    let encoded = |encoding: Encoding| encoding.encode(digest).trim_end_matches('=').to_string();
+
+    !expected.is_empty()
+        && (expected.eq_ignore_ascii_case(&encoded(Encoding::Hex))
+            || expected.eq_ignore_ascii_case(&encoded(Encoding::Base32))
+            || expected == encoded(Encoding::Base64))
+}
+
+/// Hash function used inside an HMAC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacAlgorithm {
+    /// Still the default for one-time passwords, where its collisions do not matter
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl MacAlgorithm {
+    /// The HMAC of `message` with a key of any length
+    pub fn mac(self, key: &[u8], message: &[u8]) -> Vec<u8> {
+        match self {
+            MacAlgorithm::Sha1 => {
+                let mut mac = Hmac::<sha1::Sha1>::new_from_slice(key).unwrap();
+                mac.update(message);
+                mac.finalize().into_bytes().to_vec()
+            }
+            MacAlgorithm::Sha256 => {
+                let mut mac = Hmac::<sha2::Sha256>::new_from_slice(key).unwrap();
+                mac.update(message);
+                mac.finalize().into_bytes().to_vec()
+            }
+            MacAlgorithm::Sha512 => {
+                let mut mac = Hmac::<sha2::Sha512>::new_from_slice(key).unwrap();
+                mac.update(message);
+                mac.finalize().into_bytes().to_vec()
+            }
+        }
+    }
+}
+
+/// Text form of binary data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// Lowercase hexadecimal
+    Hex,
+    /// Standard alphabet with padding
+    Base64,
+    /// RFC 4648 alphabet with padding
+    Base32,
+}
+
+impl Encoding {
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Hex => HEXLOWER.encode(bytes),
+            Encoding::Base64 => BASE64.encode(bytes),
+            Encoding::Base32 => BASE32.encode(bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(algorithm: Algorithm, input: &[u8]) -> String {
+        Encoding::Hex.encode(&algorithm.digest(input))
+    }
+
+    #[test]
+    fn known_digests() {
+        // 빈 문자열과 "abc"의 알려진 값
+        let expected = [
+            (
+                Algorithm::Md5,
+                "d41d8cd98f00b204e9800998ecf8427e",
+                "900150983cd24fb0d6963f7d28e17f72",
+            ),
+            (
+                Algorithm::Sha1,
+                "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+                "a9993e364706816aba3e25717850c26c9cd0d89d",
+            ),
+            (
+                Algorithm::Sha224,
+                "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f",
+                "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+            ),
+            (
+                Algorithm::Sha256,
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                Algorithm::Sha384,
+                "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da\
+                 274edebfe76f65fbd51ad2f14898b95b",
+                "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+                 8086072ba1e7cc2358baeca134c825a7",
+            ),
+            (
+                Algorithm::Sha512,
+                "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+                 47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e",
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                 2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            ),
+            (
+                Algorithm::Sha3_256,
+                "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+                "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+            ),
+            (
+                Algorithm::Sha3_512,
+                "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a6\
+                 15b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26",
+                "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e\
+                 10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0",
+            ),
+            (
+                Algorithm::Blake2b,
+                "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419\
+                 d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce",
+                "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+                 7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+            ),
+            (
+                Algorithm::Blake3,
+                "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+                "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+            ),
+            (Algorithm::Crc32, "00000000", "352441c2"),
+            (Algorithm::Xxh64, "ef46db3751d8e999", "44bc2cf5ad770999"),
+            (Algorithm::Xxh3, "2d06800538d394c2", "78af5f94892f3950"),
+        ];
+
+        for (algorithm, empty, abc) in expected {
+            assert_eq!(hex(algorithm, b""), empty, "{:?} of \"\"", algorithm);
+            assert_eq!(hex(algorithm, b"abc"), abc, "{:?} of \"abc\"", algorithm);
+        }
+    }
+
+    #[test]
+    fn pieces_match_whole() {
+        let mut hasher = Algorithm::Sha256.hasher();
+        hasher.update(b"a");
+        hasher.update(b"bc");
+        assert_eq!(hasher.finalize(), Algorithm::Sha256.digest(b"abc"));
+    }
+
+    #[test]
+    fn encodings() {
+        // RFC 4648의 예시
+        assert_eq!(Encoding::Hex.encode(b"foobar"), "666f6f626172");
+        assert_eq!(Encoding::Base64.encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(Encoding::Base64.encode(b"fooba"), "Zm9vYmE=");
+        assert_eq!(Encoding::Base32.encode(b"foobar"), "MZXW6YTBOI======");
+
+        let digest = Algorithm::Sha256.digest(b"abc");
+        assert_eq!(
+            Encoding::Base64.encode(&digest),
+            "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0="
+        );
+    }
+
+    #[test]
+    fn matches_any_encoding() {
+        let digest = Algorithm::Sha256.digest(b"abc");
+
+        assert!(matches(
+            &digest,
+            "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"
+        ));
+        assert!(matches(
+            &digest,
+            " ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0 "
+        ));
+        assert!(matches(
+            &digest,
+            &Encoding::Base32.encode(&digest).to_lowercase()
+        ));
+        assert!(!matches(
+            &digest,
+            "UNGWV48BZ+PBQUDEXA4II7ADYAOWF3QCTBD/YFIAFA0="
+        ));
+        assert!(!matches(&digest, ""));
+        assert!(!matches(&digest, "ba7816bf"));
+    }
+
+    #[test]
+    fn hmac_rfc4231() {
+        // RFC 4231 테스트 케이스 2
+        let (key, message) = (b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            Encoding::Hex.encode(&MacAlgorithm::Sha256.mac(key, message)),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            Encoding::Hex.encode(&MacAlgorithm::Sha512.mac(key, message)),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+pub mod barcode;
+pub mod braille;
+pub mod color;
+pub mod crypto;
+pub mod encoding;
+pub mod font;
+pub mod framepack;
+pub mod frames;
+pub mod gradient;
+pub mod hash;
+pub mod life;
+pub mod lsystem;
+pub mod otp;
+pub mod password;
+pub mod qrdecode;
+pub mod quantize;
+pub mod seed;
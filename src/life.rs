@@ -0,0 +1,405 @@
+//! Cellular automata, life-like rules on a grid and elementary rules on a row
+//!
+//! Elementary rules fill the board one row per generation from the top,
+//! then scroll it up once it is full.
+
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct LifeError(String);
+
+impl fmt::Display for LifeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for LifeError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Born or surviving with the marked numbers of live neighbours out of 8
+    LifeLike {
+        birth: [bool; 9],
+        survival: [bool; 9],
+    },
+    /// Wolfram's numbering of the rules on a row with 3 cell neighbourhoods
+    Elementary(u8),
+}
+
+/// Well-known life-like rules by name
+const NAMED_RULES: [(&str, &str); 8] = [
+    ("life", "B3/S23"),
+    ("conway", "B3/S23"),
+    ("highlife", "B36/S23"),
+    ("seeds", "B2/S"),
+    ("dayandnight", "B3678/S34678"),
+    ("maze", "B3/S12345"),
+    ("replicator", "B1357/S1357"),
+    ("lifewithoutdeath", "B3/S012345678"),
+];
+
+impl Rule {
+    pub const LIFE: Rule = Rule::LifeLike {
+        birth: [false, false, false, true, false, false, false, false, false],
+        survival: [false, false, true, true, false, false, false, false, false],
+    };
+}
+
+fn parse_counts(digits: &str) -> Result<[bool; 9], LifeError> {
+    let mut counts = [false; 9];
+    for c in digits.chars() {
+        match c.to_digit(10) {
+            Some(count) if count <= 8 => counts[count as usize] = true,
+            _ => {
+                return Err(LifeError(format!(
+                    "`{}` is not a neighbour count from 0 to 8",
+                    c
+                )))
+            }
+        }
+    }
+
+    Ok(counts)
+}
+
+impl FromStr for Rule {
+    type Err = LifeError;
+
+    /// `B3/S23`, `S23/B3`, `23/3`, a name like `highlife`, or `rule 30` for elementary rules
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let compact: String = input
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-' && *c != '_')
+            .collect::<String>()
+            .to_lowercase();
+
+        if let Some((_, rule)) = NAMED_RULES.iter().find(|(name, _)| *name == compact) {
+            return rule.parse();
+        }
+
+        let number = compact
+            .strip_prefix("rule")
+            .or_else(|| compact.strip_prefix('w'))
+            .or_else(|| compact.strip_prefix('r'))
+            .unwrap_or(&compact);
+        if !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+            && !compact.contains('/')
+        {
+            return match number.parse::<u8>() {
+                Ok(number) => Ok(Rule::Elementary(number)),
+                Err(_) => Err(LifeError(
+                    "elementary rules are numbered from 0 to 255".to_string(),
+                )),
+            };
+        }
+
+        let (birth, survival) = if compact.starts_with('b') || compact.starts_with('s') {
+            // B와 S 사이의 `/`는 생략할 수 있음
+            let split = compact[1..]
+                .find(['b', 's'])
+                .map(|index| index + 1)
+                .ok_or_else(|| LifeError(format!("`{}` is not a B/S rulestring", input.trim())))?;
+            let (first, second) = compact.split_at(split);
+            let first = first.trim_end_matches('/');
+            let (birth, survival) = if first.starts_with('b') {
+                (first, second)
+            } else {
+                (second, first)
+            };
+            if !birth.starts_with('b') || !survival.starts_with('s') {
+                return Err(LifeError(format!(
+                    "`{}` is not a B/S rulestring",
+                    input.trim()
+                )));
+            }
+            (birth[1..].to_string(), survival[1..].to_string())
+        } else if let Some((survival, birth)) = compact.split_once('/') {
+            // 예전 표기법은 생존/탄생 순서
+            (birth.to_string(), survival.to_string())
+        } else {
+            return Err(LifeError(format!(
+                "`{}` is not a rulestring like B3/S23 or rule 30",
+                input.trim()
+            )));
+        };
+
+        Ok(Rule::LifeLike {
+            birth: parse_counts(&birth)?,
+            survival: parse_counts(&survival)?,
+        })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::LifeLike { birth, survival } => {
+                let digits = |counts: &[bool; 9]| -> String {
+                    (0..9)
+                        .filter(|&count| counts[count])
+                        .map(|count| char::from(b'0' + count as u8))
+                        .collect()
+                };
+                write!(f, "B{}/S{}", digits(birth), digits(survival))
+            }
+            Rule::Elementary(number) => write!(f, "Rule {}", number),
+        }
+    }
+}
+
+/// A pattern read from run length encoded text
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pub width: usize,
+    pub height: usize,
+    /// Live cells as (x, y)
+    pub cells: Vec<(usize, usize)>,
+    /// The rule given in the header, if any
+    pub rule: Option<Rule>,
+}
+
+impl FromStr for Pattern {
+    type Err = LifeError;
+
+    /// The RLE format, with an optional `x = 3, y = 3, rule = B3/S23` header
+    ///
+    /// The header may be on the same line as the pattern, since slash command options
+    /// have no line breaks. Any letter other than `b` and `.` is a live cell.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+
+        let mut rule = None;
+        let mut body = lines.join("");
+        if let Some(first) = lines.first().filter(|line| line.starts_with(['x', 'X'])) {
+            let rest = lines[1..].join("");
+            let mut header = first.to_string();
+            let mut body_start = String::new();
+
+            // 헤더의 마지막 값 뒤에 패턴이 이어지는 경우
+            if let Some((last_start, last)) = header.rsplit_once('=') {
+                let last = last.trim_start();
+                if let Some((value, tail)) = last.split_once(char::is_whitespace) {
+                    body_start = tail.trim().to_string();
+                    header = format!("{}= {}", last_start, value);
+                }
+            }
+
+            for pair in header.split(',') {
+                if let Some((key, value)) = pair.split_once('=') {
+                    if key.trim().eq_ignore_ascii_case("rule") {
+                        // Golly의 `:T64,64` 같은 경계 지정은 무시
+                        rule = Some(value.split(':').next().unwrap_or_default().trim().parse()?);
+                    }
+                }
+            }
+            body = body_start + &rest;
+        }
+
+        let mut cells = vec![];
+        let (mut x, mut y) = (0, 0);
+        let (mut width, mut height) = (0, 0);
+        let mut count = String::new();
+        for c in body.chars() {
+            if c.is_whitespace() {
+                continue;
+            }
+            if c.is_ascii_digit() {
+                count.push(c);
+                continue;
+            }
+
+            let run: usize = if count.is_empty() {
+                1
+            } else {
+                count
+                    .parse()
+                    .ok()
+                    .filter(|run| *run <= 10000)
+                    .ok_or_else(|| LifeError(format!("the run length {} is too long", count)))?
+            };
+            count.clear();
+
+            match c {
+                '!' => break,
+                '$' => {
+                    y += run;
+                    x = 0;
+                }
+                'b' | '.' => x += run,
+                c if c.is_ascii_alphabetic() => {
+                    cells.extend((x..x + run).map(|x| (x, y)));
+                    x += run;
+                    width = width.max(x);
+                    height = height.max(y + 1);
+                }
+                c => return Err(LifeError(format!("`{}` is not part of an RLE pattern", c))),
+            }
+        }
+
+        if cells.is_empty() {
+            return Err(LifeError("the pattern has no live cells".to_string()));
+        }
+
+        Ok(Pattern {
+            width,
+            height,
+            cells,
+            rule,
+        })
+    }
+}
+
+/// Cells on a rectangular board, row by row
+pub struct Board {
+    width: usize,
+    height: usize,
+    cells: Vec<bool>,
+    wrap: bool,
+    rule: Rule,
+    /// The row of the latest generation of an elementary rule
+    row: usize,
+}
+
+impl Board {
+    /// An empty board, with edges that either wrap around or are always dead
+    pub fn new(width: usize, height: usize, wrap: bool, rule: Rule) -> Self {
+        Board {
+            width,
+            height,
+            cells: vec![false; width * height],
+            wrap,
+            rule,
+            row: 0,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn cells(&self) -> &[bool] {
+        &self.cells
+    }
+
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|&&alive| alive).count()
+    }
+
+    /// Make every cell alive with the given probability, only the first row for elementary rules
+    pub fn randomize(&mut self, rng: &mut impl Rng, density: f64) {
+        let count = match self.rule {
+            Rule::LifeLike { .. } => self.cells.len(),
+            Rule::Elementary(_) => self.width,
+        };
+        for cell in &mut self.cells[..count] {
+            *cell = rng.gen_bool(density);
+        }
+    }
+
+    /// Put a pattern in the middle of the board, or the middle of the first row for elementary rules
+    pub fn place(&mut self, pattern: &Pattern) -> Result<(), LifeError> {
+        let height = match self.rule {
+            Rule::LifeLike { .. } => pattern.height,
+            Rule::Elementary(_) => 1,
+        };
+        if pattern.width > self.width || height > self.height {
+            return Err(LifeError(format!(
+                "the pattern is {}×{}, which doesn't fit on the {}×{} board",
+                pattern.width, height, self.width, self.height
+            )));
+        }
+
+        let left = (self.width - pattern.width) / 2;
+        let top = match self.rule {
+            Rule::LifeLike { .. } => (self.height - pattern.height) / 2,
+            Rule::Elementary(_) => 0,
+        };
+        for &(x, y) in &pattern.cells {
+            // 기본 규칙은 한 줄이므로 패턴의 첫 줄만 사용
+            if y < height {
+                self.cells[(top + y) * self.width + left + x] = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get(&self, x: isize, y: isize) -> bool {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let (x, y) = if self.wrap {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else if (0..width).contains(&x) && (0..height).contains(&y) {
+            (x, y)
+        } else {
+            return false;
+        };
+
+        self.cells[(y * width + x) as usize]
+    }
+
+    /// Advance by one generation
+    pub fn step(&mut self) {
+        match self.rule {
+            Rule::LifeLike { birth, survival } => {
+                let mut next = vec![false; self.cells.len()];
+                for y in 0..self.height as isize {
+                    for x in 0..self.width as isize {
+                        let neighbours = [
+                            (-1, -1),
+                            (0, -1),
+                            (1, -1),
+                            (-1, 0),
+                            (1, 0),
+                            (-1, 1),
+                            (0, 1),
+                            (1, 1),
+                        ]
+                        .iter()
+                        .filter(|(dx, dy)| self.get(x + dx, y + dy))
+                        .count();
+
+                        let index = y as usize * self.width + x as usize;
+                        next[index] = if self.cells[index] {
+                            survival[neighbours]
+                        } else {
+                            birth[neighbours]
+                        };
+                    }
+                }
+                self.cells = next;
+            }
+            Rule::Elementary(number) => {
+                let row = self.row as isize;
+                let next: Vec<bool> = (0..self.width as isize)
+                    .map(|x| {
+                        let neighbourhood = (self.get(x - 1, row) as u8) << 2
+                            | (self.get(x, row) as u8) << 1
+                            | self.get(x + 1, row) as u8;
+                        number >> neighbourhood & 1 == 1
+                    })
+                    .collect();
+
+                // 보드가 가득 차면 한 줄씩 위로 밀어 올림
+                if self.row + 1 < self.height {
+                    self.row += 1;
+                } else {
+                    self.cells.copy_within(self.width.., 0);
+                }
+                let start = self.row * self.width;
+                self.cells[start..start + self.width].copy_from_slice(&next);
+            }
+        }
+    }
+}
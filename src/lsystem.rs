@@ -0,0 +1,311 @@
+//! Lindenmayer systems and the turtle that draws them
+//!
+//! The turtle moves forward drawing a line on `F` and `G`, moves without drawing on `f`,
+//! turns left on `+` and right on `-`, turns around on `|`, and saves and restores
+//! its state on `[` and `]`. Every other symbol is only used by the rules.
+
+use crate::color::{self, Color};
+use image::{Rgba, RgbaImage};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct LSystemError(String);
+
+impl fmt::Display for LSystemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for LSystemError {}
+
+/// A line drawn by the turtle, from the first point to the second
+pub type Line = ((f64, f64), (f64, f64));
+
+/// Production rules like `F=F+F--F+F, X=F[+X]F` or `F->FF`, separated by commas, semicolons or spaces
+pub fn parse_rules(input: &str) -> Result<HashMap<char, String>, LSystemError> {
+    let mut rules = HashMap::new();
+
+    for rule in input
+        .split([',', ';', ' ', '\n'])
+        .filter(|rule| !rule.is_empty())
+    {
+        let (symbol, replacement) = rule
+            .split_once("->")
+            .or_else(|| rule.split_once('→'))
+            .or_else(|| rule.split_once('='))
+            .ok_or_else(|| LSystemError(format!("`{}` is not a rule like F=F+F", rule)))?;
+
+        let mut symbol = symbol.chars();
+        let (Some(symbol), None) = (symbol.next(), symbol.next()) else {
+            return Err(LSystemError(format!(
+                "`{}` should replace a single symbol",
+                rule
+            )));
+        };
+        if rules.insert(symbol, replacement.to_string()).is_some() {
+            return Err(LSystemError(format!("`{}` has more than one rule", symbol)));
+        }
+    }
+
+    Ok(rules)
+}
+
+/// Apply the rules to every symbol at once, `iterations` times
+///
+/// Fails before building a string longer than `max_length` symbols.
+pub fn expand(
+    axiom: &str,
+    rules: &HashMap<char, String>,
+    iterations: u32,
+    max_length: usize,
+) -> Result<String, LSystemError> {
+    let mut current = axiom.to_string();
+
+    for iteration in 1..=iterations {
+        // 문자열을 만들기 전에 길이를 먼저 계산해서 한도를 넘지 않게 함
+        let length = current.chars().try_fold(0usize, |length, symbol| {
+            let added = rules
+                .get(&symbol)
+                .map_or(1, |replacement| replacement.chars().count());
+            length
+                .checked_add(added)
+                .filter(|&length| length <= max_length)
+        });
+        if length.is_none() {
+            return Err(LSystemError(format!(
+                "after {} iterations the string would be longer than {} symbols",
+                iteration, max_length
+            )));
+        }
+
+        let mut next = String::with_capacity(current.len());
+        for symbol in current.chars() {
+            match rules.get(&symbol) {
+                Some(replacement) => next.push_str(replacement),
+                None => next.push(symbol),
+            }
+        }
+        current = next;
+    }
+
+    Ok(current)
+}
+
+/// The lines drawn by the turtle, which starts at the origin facing `heading` degrees
+///
+/// Angles are counterclockwise with y pointing up, and every step has a length of 1.
+pub fn trace(commands: &str, angle: f64, heading: f64) -> Vec<Line> {
+    let mut lines = vec![];
+    let mut stack = vec![];
+    let (mut position, mut heading) = ((0.0, 0.0), heading);
+
+    for command in commands.chars() {
+        match command {
+            'F' | 'G' | 'f' => {
+                let (sin, cos) = heading.to_radians().sin_cos();
+                let next = (position.0 + cos, position.1 + sin);
+                if command != 'f' {
+                    lines.push((position, next));
+                }
+                position = next;
+            }
+            '+' => heading += angle,
+            '-' => heading -= angle,
+            '|' => heading += 180.0,
+            '[' => stack.push((position, heading)),
+            // 짝이 맞지 않는 `]`는 무시
+            ']' => {
+                if let Some(state) = stack.pop() {
+                    (position, heading) = state;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    lines
+}
+
+/// A well-known L-system with the angle and starting heading it is usually drawn with
+#[derive(Debug, Clone, Copy)]
+pub struct Preset {
+    pub axiom: &'static str,
+    pub rules: &'static str,
+    pub angle: f64,
+    pub heading: f64,
+    pub iterations: u32,
+}
+
+pub const KOCH_SNOWFLAKE: Preset = Preset {
+    axiom: "F--F--F",
+    rules: "F=F+F--F+F",
+    angle: 60.0,
+    heading: 0.0,
+    iterations: 4,
+};
+
+pub const SIERPINSKI_TRIANGLE: Preset = Preset {
+    axiom: "F-G-G",
+    rules: "F=F-G+F+G-F, G=GG",
+    angle: 120.0,
+    heading: 0.0,
+    iterations: 6,
+};
+
+pub const SIERPINSKI_ARROWHEAD: Preset = Preset {
+    axiom: "F",
+    rules: "F=G-F-G, G=F+G+F",
+    angle: 60.0,
+    heading: 0.0,
+    iterations: 7,
+};
+
+pub const DRAGON_CURVE: Preset = Preset {
+    axiom: "F",
+    rules: "F=F+G, G=F-G",
+    angle: 90.0,
+    heading: 0.0,
+    iterations: 12,
+};
+
+pub const HILBERT_CURVE: Preset = Preset {
+    axiom: "X",
+    rules: "X=+YF-XFX-FY+, Y=-XF+YFY+FX-",
+    angle: 90.0,
+    heading: 0.0,
+    iterations: 6,
+};
+
+pub const FRACTAL_PLANT: Preset = Preset {
+    axiom: "X",
+    rules: "X=F+[[X]-X]-F[-FX]+X, F=FF",
+    angle: 25.0,
+    heading: 65.0,
+    iterations: 6,
+};
+
+/// Longest piece a line is split into when drawing, so a long diagonal line only
+/// looks at the pixels near it instead of its whole bounding box
+const PIECE_LENGTH: f64 = 8.0;
+
+/// The pieces of the line from `a` to `b`, each at most [`PIECE_LENGTH`] long
+fn pieces((ax, ay): (f64, f64), (bx, by): (f64, f64)) -> impl Iterator<Item = Line> {
+    let count = ((bx - ax).hypot(by - ay) / PIECE_LENGTH).ceil().max(1.0) as usize;
+    let point = move |i: usize| {
+        let t = i as f64 / count as f64;
+        (ax + (bx - ax) * t, ay + (by - ay) * t)
+    };
+
+    (0..count).map(move |i| (point(i), point(i + 1)))
+}
+
+/// Draw the lines with anti-aliased edges, scaled to fit inside the image with a margin
+///
+/// `thickness` is in pixels. The turtle's y axis points up, so the drawing is flipped
+/// to the image's. Fails without drawing when more than `max_area` pixels would be
+/// looked at, counting a pixel again for every line near it.
+pub fn render(
+    lines: &[Line],
+    width: u32,
+    height: u32,
+    thickness: f64,
+    foreground: Color,
+    background: Color,
+    max_area: u64,
+) -> Result<RgbaImage, LSystemError> {
+    let (w, h) = (width as usize, height as usize);
+    let mut coverage = vec![0f32; w * h];
+
+    let points = lines.iter().flat_map(|&(start, end)| [start, end]);
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (0f64, 0f64, 0f64, 0f64);
+    for (x, y) in points {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+
+    let margin = thickness + 4.0;
+    let scale = ((width as f64 - 2.0 * margin) / (max_x - min_x).max(1e-9))
+        .min((height as f64 - 2.0 * margin) / (max_y - min_y).max(1e-9));
+    // 그림을 이미지 가운데에 놓음
+    let offset_x = (width as f64 - (max_x - min_x) * scale) / 2.0;
+    let offset_y = (height as f64 - (max_y - min_y) * scale) / 2.0;
+    let to_pixel = |(x, y): (f64, f64)| {
+        (
+            offset_x + (x - min_x) * scale,
+            height as f64 - offset_y - (y - min_y) * scale,
+        )
+    };
+
+    let radius = thickness / 2.0;
+    let reach = radius + 1.0;
+    // 조각 주변에서 살펴볼 픽셀의 범위
+    let bounds = |(ax, ay): (f64, f64), (bx, by): (f64, f64)| {
+        let left = (ax.min(bx) - reach).floor().max(0.0) as usize;
+        let top = (ay.min(by) - reach).floor().max(0.0) as usize;
+        let right = ((ax.max(bx) + reach).ceil().max(0.0) as usize).min(w);
+        let bottom = ((ay.max(by) + reach).ceil().max(0.0) as usize).min(h);
+        (left, top, right.max(left), bottom.max(top))
+    };
+    let pixel_pieces = || {
+        lines
+            .iter()
+            .flat_map(|&(start, end)| pieces(to_pixel(start), to_pixel(end)))
+    };
+
+    // 그리기 전에 살펴볼 픽셀 수를 세어 너무 오래 걸리는 그림은 거절함
+    let mut area = 0u64;
+    for (a, b) in pixel_pieces() {
+        let (left, top, right, bottom) = bounds(a, b);
+        area += ((right - left) * (bottom - top)) as u64;
+        if area > max_area {
+            return Err(LSystemError(
+                "The drawing is too detailed, use fewer iterations or thinner lines".into(),
+            ));
+        }
+    }
+
+    for ((ax, ay), (bx, by)) in pixel_pieces() {
+        let (dx, dy) = (bx - ax, by - ay);
+        let length_squared = dx * dx + dy * dy;
+        let (left, top, right, bottom) = bounds((ax, ay), (bx, by));
+
+        for y in top..bottom {
+            for x in left..right {
+                // 픽셀 중심에서 선분까지의 거리로 덮인 정도를 근사
+                let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+                let t = if length_squared > 0.0 {
+                    (((px - ax) * dx + (py - ay) * dy) / length_squared).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let distance = (px - ax - t * dx).hypot(py - ay - t * dy);
+                let covered = (radius + 0.5 - distance).clamp(0.0, 1.0) as f32;
+
+                let cell = &mut coverage[y * w + x];
+                *cell = cell.max(covered);
+            }
+        }
+    }
+
+    let (fr, fg, fb) = foreground.to_unit();
+    let (br, bg, bb) = background.to_unit();
+    let (fa, ba) = (foreground.a as f64, background.a as f64);
+    Ok(RgbaImage::from_fn(width, height, |x, y| {
+        let t = coverage[y as usize * w + x as usize] as f64;
+        // 감마를 고려해 선형 RGB에서 섞음
+        let mix = |f: f64, b: f64| {
+            let linear = color::to_linear(b) + (color::to_linear(f) - color::to_linear(b)) * t;
+            (color::from_linear(linear) * 255.0).round() as u8
+        };
+        Rgba([
+            mix(fr, br),
+            mix(fg, bg),
+            mix(fb, bb),
+            (ba + (fa - ba) * t).round() as u8,
+        ])
+    }))
+}
@@ -1,8 +1,10 @@
 mod commands;
 
+use arcum::framepack::FramePack;
 use dotenv::dotenv;
 use poise::serenity_prelude as serenity;
 use poise::serenity_prelude::model::id::{ChannelId, UserId};
+use std::collections::HashSet;
 use std::env;
 use std::num::NonZeroU64;
 use std::sync::Arc;
@@ -21,6 +23,8 @@ pub struct Data {
     wordchain_check: Arc<Mutex<bool>>,
     wordchain_thread: Arc<Mutex<ChannelId>>,
     wordchain_playing: Arc<Mutex<bool>>,
+    badapple: Option<Arc<FramePack>>,
+    playing_channels: Arc<Mutex<HashSet<ChannelId>>>,
 }
 
 async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
@@ -49,6 +53,7 @@ async fn main() {
             commands::help(),
             commands::ping(),
             commands::badapple(),
+            commands::animate(),
             commands::tts(),
             commands::matrix(),
             commands::alarm(),
@@ -56,12 +61,34 @@ async fn main() {
             commands::maze(),
             commands::exit(),
             commands::ptf(),
+            commands::lsystem(),
             commands::bwimg(),
             commands::rgbimg(),
+            commands::noise(),
+            commands::life(),
             commands::hexcode(),
+            commands::palette(),
+            commands::colors(),
+            commands::contrast(),
+            commands::colorblind(),
+            commands::qr(),
+            commands::barcode(),
+            commands::qrread(),
+            commands::hash(),
             commands::sha256(),
             commands::argon2(),
             commands::verify(),
+            commands::hmac(),
+            commands::kdf(),
+            commands::password(),
+            commands::totp(),
+            commands::encrypt(),
+            commands::decrypt(),
+            commands::keypair(),
+            commands::sign(),
+            commands::verify_signature(),
+            commands::encode(),
+            commands::decode(),
             commands::gradient(),
         ],
         prefix_options: poise::PrefixFrameworkOptions {
@@ -108,6 +135,18 @@ async fn main() {
             Box::pin(async move {
                 println!("Logged in as {}", _ready.user.name);
                 poise::builtins::register_globally(ctx, &framework.options().commands).await?;
+
+                // Load the Bad Apple!! frames once so playback starts right away
+                let badapple_path = env::var("BADAPPLE_PACK")
+                    .unwrap_or_else(|_| "output/badapple.pack".to_string());
+                let badapple = match FramePack::open(&badapple_path) {
+                    Ok(pack) => Some(Arc::new(pack)),
+                    Err(e) => {
+                        println!("Failed to load {}: {}", badapple_path, e);
+                        None
+                    }
+                };
+
                 Ok(Data {
                     wordchain_message: Arc::new(Mutex::new(vec![])),
                     wordchain_player: Arc::new(Mutex::new((
@@ -120,6 +159,8 @@ async fn main() {
                         NonZeroU64::new(1).unwrap(),
                     ))),
                     wordchain_playing: Arc::new(Mutex::new(false)),
+                    badapple,
+                    playing_channels: Arc::new(Mutex::new(HashSet::new())),
                 })
             })
         })
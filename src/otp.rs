@@ -0,0 +1,437 @@
+//! One-time passwords of RFC 4226 (HOTP) and RFC 6238 (TOTP), and the `otpauth://`
+//! URIs authenticator apps enrol them with
+
+use crate::encoding::Codec;
+use crate::hash::MacAlgorithm;
+use data_encoding::BASE32_NOPAD;
+use rand::Rng;
+use std::fmt;
+
+pub const DEFAULT_PERIOD: u64 = 30;
+pub const DEFAULT_DIGITS: u32 = 6;
+/// Length of generated secrets, the size of an HMAC-SHA1 key as RFC 4226 recommends
+pub const SECRET_LENGTH: usize = 20;
+/// Largest HOTP counter, which leaves room to count ahead and to tell the steps as `i64`
+pub const MAX_COUNTER: u64 = i64::MAX as u64;
+
+#[derive(Debug)]
+pub struct OtpError(String);
+
+impl fmt::Display for OtpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for OtpError {}
+
+fn error(message: impl Into<String>) -> OtpError {
+    OtpError(message.into())
+}
+
+/// What the codes are counted from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A new code every `period` seconds since the Unix epoch
+    Totp { period: u64 },
+    /// A new code each time the counter goes up
+    Hotp { counter: u64 },
+}
+
+#[derive(Debug, Clone)]
+pub struct Otp {
+    pub secret: Vec<u8>,
+    pub algorithm: MacAlgorithm,
+    pub digits: u32,
+    pub kind: Kind,
+    pub issuer: Option<String>,
+    pub account: Option<String>,
+}
+
+impl Otp {
+    /// TOTP with the settings every authenticator app supports, SHA1, 6 digits and 30 seconds
+    pub fn new(secret: Vec<u8>) -> Self {
+        Otp {
+            secret,
+            algorithm: MacAlgorithm::Sha1,
+            digits: DEFAULT_DIGITS,
+            kind: Kind::Totp {
+                period: DEFAULT_PERIOD,
+            },
+            issuer: None,
+            account: None,
+        }
+    }
+
+    /// A base32 secret or an `otpauth://` URI
+    pub fn parse(input: &str) -> Result<Self, OtpError> {
+        let input = input.trim();
+        let otp = match input.split_once("://") {
+            Some((scheme, rest)) if scheme.eq_ignore_ascii_case("otpauth") => from_uri(rest)?,
+            _ => Otp::new(parse_secret(input)?),
+        };
+        otp.check()?;
+
+        Ok(otp)
+    }
+
+    /// Whether the settings are ones authenticator apps can use
+    pub fn check(&self) -> Result<(), OtpError> {
+        if self.secret.is_empty() {
+            return Err(error("The secret is empty"));
+        }
+        if !(6..=8).contains(&self.digits) {
+            return Err(error(
+                "The number of digits must be between 6 and 8 inclusive",
+            ));
+        }
+        match self.kind {
+            Kind::Totp { period } if !(1..=3600).contains(&period) => {
+                return Err(error(
+                    "The period must be between 1 and 3600 seconds inclusive",
+                ));
+            }
+            Kind::Hotp { counter } if counter > MAX_COUNTER => {
+                return Err(error(format!(
+                    "The counter must be at most {}",
+                    MAX_COUNTER
+                )));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// The code for a counter value, which for TOTP is the number of periods elapsed
+    pub fn code_at(&self, counter: u64) -> String {
+        let mac = self.algorithm.mac(&self.secret, &counter.to_be_bytes());
+
+        // 마지막 바이트의 낮은 4비트가 가리키는 곳에서 31비트를 꺼냄
+        let offset = (mac[mac.len() - 1] & 0x0f) as usize;
+        let value = u32::from_be_bytes(mac[offset..offset + 4].try_into().unwrap()) & 0x7fff_ffff;
+
+        format!(
+            "{:0width$}",
+            value % 10u32.pow(self.digits),
+            width = self.digits as usize
+        )
+    }
+
+    /// The counter value at `time` seconds since the Unix epoch
+    pub fn counter(&self, time: u64) -> u64 {
+        match self.kind {
+            Kind::Totp { period } => time / period,
+            Kind::Hotp { counter } => counter,
+        }
+    }
+
+    /// The code at `time` seconds since the Unix epoch
+    pub fn code(&self, time: u64) -> String {
+        self.code_at(self.counter(time))
+    }
+
+    /// Seconds until the code changes, `None` for HOTP
+    pub fn remaining(&self, time: u64) -> Option<u64> {
+        match self.kind {
+            Kind::Totp { period } => Some(period - time % period),
+            Kind::Hotp { .. } => None,
+        }
+    }
+
+    /// How many steps from the current one `code` belongs to, looking `window` steps
+    /// both ways for TOTP and only ahead for HOTP since its counter never goes back
+    pub fn verify(&self, code: &str, time: u64, window: u64) -> Option<i64> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if code.len() != self.digits as usize {
+            return None;
+        }
+
+        let current = self.counter(time);
+        let first = match self.kind {
+            Kind::Totp { .. } => current.saturating_sub(window),
+            Kind::Hotp { .. } => current,
+        };
+
+        (first..=current.saturating_add(window).min(MAX_COUNTER))
+            .find(|&counter| constant_time_eq(self.code_at(counter).as_bytes(), code.as_bytes()))
+            .map(|counter| counter as i64 - current as i64)
+    }
+
+    /// The `otpauth://` URI, the text of the QR code apps scan
+    pub fn uri(&self) -> String {
+        let label = match (&self.issuer, &self.account) {
+            (Some(issuer), Some(account)) => format!("{}:{}", escape(issuer), escape(account)),
+            (Some(name), None) | (None, Some(name)) => escape(name),
+            (None, None) => String::new(),
+        };
+        let (kind, count) = match self.kind {
+            Kind::Totp { period } => ("totp", format!("period={}", period)),
+            Kind::Hotp { counter } => ("hotp", format!("counter={}", counter)),
+        };
+
+        let mut uri = format!(
+            "otpauth://{}/{}?secret={}&algorithm={}&digits={}&{}",
+            kind,
+            label,
+            BASE32_NOPAD.encode(&self.secret),
+            algorithm_name(self.algorithm),
+            self.digits,
+            count
+        );
+        if let Some(issuer) = &self.issuer {
+            uri += &format!("&issuer={}", escape(issuer));
+        }
+
+        uri
+    }
+}
+
+/// The name of the algorithm as written in `otpauth://` URIs
+pub fn algorithm_name(algorithm: MacAlgorithm) -> &'static str {
+    match algorithm {
+        MacAlgorithm::Sha1 => "SHA1",
+        MacAlgorithm::Sha256 => "SHA256",
+        MacAlgorithm::Sha512 => "SHA512",
+    }
+}
+
+/// A base32 secret, ignoring case, padding, spaces and dashes
+pub fn parse_secret(input: &str) -> Result<Vec<u8>, OtpError> {
+    let compact: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect();
+
+    BASE32_NOPAD
+        .decode(compact.trim_end_matches('=').to_uppercase().as_bytes())
+        .map_err(|e| error(format!("Invalid base32 secret, {}", e)))
+}
+
+pub fn generate_secret(rng: &mut impl Rng) -> Vec<u8> {
+    let mut secret = vec![0; SECRET_LENGTH];
+    rng.fill(&mut secret[..]);
+    secret
+}
+
+/// Parse what comes after `otpauth://`, like `totp/Issuer:account?secret=...`
+fn from_uri(rest: &str) -> Result<Otp, OtpError> {
+    let (kind, rest) = rest
+        .split_once('/')
+        .ok_or_else(|| error("Invalid URI, expected `otpauth://totp/...`"))?;
+    let (label, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+    let label = unescape(label)?;
+    let (label_issuer, account) = match label.split_once(':') {
+        Some((issuer, account)) => (Some(issuer.trim().to_string()), account.trim()),
+        None => (None, label.trim()),
+    };
+
+    let mut otp = Otp::new(vec![]);
+    otp.account = (!account.is_empty()).then(|| account.to_string());
+    let mut secret = None;
+    let mut period = None;
+    let mut counter = None;
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = unescape(value)?;
+        let number = |name: &str| {
+            value
+                .parse::<u64>()
+                .map_err(|_| error(format!("Invalid URI, the {} must be a number", name)))
+        };
+
+        match key.to_ascii_lowercase().as_str() {
+            "secret" => secret = Some(parse_secret(&value)?),
+            "issuer" => otp.issuer = Some(value.clone()),
+            "algorithm" => {
+                otp.algorithm = match value.to_ascii_uppercase().as_str() {
+                    "SHA1" => MacAlgorithm::Sha1,
+                    "SHA256" => MacAlgorithm::Sha256,
+                    "SHA512" => MacAlgorithm::Sha512,
+                    _ => return Err(error(format!("Unknown algorithm `{}`", value))),
+                }
+            }
+            // u64로 읽고 자르면 큰 값이 6~8로 바뀔 수 있으므로 u32로 읽음
+            "digits" => {
+                otp.digits = value.parse().map_err(|_| {
+                    error("Invalid URI, the number of digits must be between 6 and 8 inclusive")
+                })?
+            }
+            "period" => period = Some(number("period")?),
+            "counter" => counter = Some(number("counter")?),
+            _ => {}
+        }
+    }
+
+    otp.kind = match kind.to_ascii_lowercase().as_str() {
+        "totp" => Kind::Totp {
+            period: period.unwrap_or(DEFAULT_PERIOD),
+        },
+        "hotp" => Kind::Hotp {
+            counter: counter.ok_or_else(|| error("Invalid URI, HOTP needs a counter"))?,
+        },
+        _ => return Err(error(format!("Unknown OTP type `{}`", kind))),
+    };
+    otp.secret = secret.ok_or_else(|| error("Invalid URI, there is no secret"))?;
+    otp.issuer = otp
+        .issuer
+        .or(label_issuer)
+        .filter(|issuer| !issuer.is_empty());
+
+    Ok(otp)
+}
+
+fn escape(text: &str) -> String {
+    Codec::Percent.encode(text).unwrap()
+}
+
+fn unescape(text: &str) -> Result<String, OtpError> {
+    let bytes = Codec::Percent
+        .decode(text)
+        .map_err(|e| error(e.to_string()))?;
+    String::from_utf8(bytes).map_err(|_| error("Invalid URI, it is not UTF-8"))
+}
+
+/// Compare without stopping at the first difference, so timing does not leak the code
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA1_SECRET: &[u8] = b"12345678901234567890";
+    const SHA256_SECRET: &[u8] = b"12345678901234567890123456789012";
+    const SHA512_SECRET: &[u8] =
+        b"1234567890123456789012345678901234567890123456789012345678901234";
+
+    fn hotp(counter: u64) -> Otp {
+        Otp {
+            kind: Kind::Hotp { counter },
+            ..Otp::new(SHA1_SECRET.to_vec())
+        }
+    }
+
+    #[test]
+    fn hotp_rfc4226_vectors() {
+        // RFC 4226 부록 D
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583",
+            "399871", "520489",
+        ];
+        let otp = hotp(0);
+        for (counter, code) in expected.iter().enumerate() {
+            assert_eq!(otp.code_at(counter as u64), *code);
+        }
+    }
+
+    #[test]
+    fn totp_rfc6238_vectors() {
+        // RFC 6238 부록 B, 시간과 SHA1, SHA256, SHA512의 코드
+        let expected = [
+            (59, "94287082", "46119246", "90693936"),
+            (1111111109, "07081804", "68084774", "25091201"),
+            (1111111111, "14050471", "67062674", "99943326"),
+            (1234567890, "89005924", "91819424", "93441116"),
+            (2000000000, "69279037", "90698825", "38618901"),
+            (20000000000, "65353130", "77737706", "47863826"),
+        ];
+        let otp = |secret: &[u8], algorithm| Otp {
+            algorithm,
+            digits: 8,
+            ..Otp::new(secret.to_vec())
+        };
+        let sha1 = otp(SHA1_SECRET, MacAlgorithm::Sha1);
+        let sha256 = otp(SHA256_SECRET, MacAlgorithm::Sha256);
+        let sha512 = otp(SHA512_SECRET, MacAlgorithm::Sha512);
+
+        for (time, code1, code256, code512) in expected {
+            assert_eq!(sha1.code(time), code1, "SHA1 at {}", time);
+            assert_eq!(sha256.code(time), code256, "SHA256 at {}", time);
+            assert_eq!(sha512.code(time), code512, "SHA512 at {}", time);
+        }
+    }
+
+    #[test]
+    fn totp_verify_looks_both_ways() {
+        let otp = Otp::new(SHA1_SECRET.to_vec());
+        let time = 300;
+        let current = otp.counter(time);
+
+        assert_eq!(otp.verify(&otp.code(time), time, 0), Some(0));
+        assert_eq!(otp.verify(&otp.code_at(current - 1), time, 1), Some(-1));
+        assert_eq!(otp.verify(&otp.code_at(current + 1), time, 1), Some(1));
+        assert_eq!(otp.verify(&otp.code_at(current + 2), time, 1), None);
+        assert_eq!(otp.verify(&otp.code_at(current - 1), time, 0), None);
+        assert_eq!(otp.verify("12345", time, 1), None);
+    }
+
+    #[test]
+    fn hotp_verify_only_looks_ahead() {
+        let otp = hotp(5);
+
+        assert_eq!(otp.verify("254676", 0, 0), Some(0));
+        assert_eq!(otp.verify("162583", 0, 3), Some(2));
+        assert_eq!(otp.verify("338314", 0, 3), None);
+        assert_eq!(otp.verify("520489", 0, 3), None);
+    }
+
+    #[test]
+    fn hotp_counter_at_the_limit() {
+        let otp = hotp(MAX_COUNTER);
+        assert!(otp.check().is_ok());
+        assert!(hotp(MAX_COUNTER + 1).check().is_err());
+
+        let code = otp.code_at(MAX_COUNTER);
+        assert_eq!(otp.verify(&code, 0, 10), Some(0));
+
+        let before = hotp(MAX_COUNTER - 1);
+        assert_eq!(before.verify(&code, 0, 10), Some(1));
+    }
+
+    #[test]
+    fn uri_round_trip() {
+        let uri = "otpauth://totp/ACME%20Co:john@example.com\
+                   ?secret=JBSWY3DPEHPK3PXP&issuer=ACME%20Co&algorithm=SHA256&digits=8&period=60";
+        let otp = Otp::parse(uri).unwrap();
+
+        assert_eq!(otp.secret, b"Hello!\xde\xad\xbe\xef");
+        assert_eq!(otp.algorithm, MacAlgorithm::Sha256);
+        assert_eq!(otp.digits, 8);
+        assert_eq!(otp.kind, Kind::Totp { period: 60 });
+        assert_eq!(otp.issuer.as_deref(), Some("ACME Co"));
+        assert_eq!(otp.account.as_deref(), Some("john@example.com"));
+
+        let again = Otp::parse(&otp.uri()).unwrap();
+        assert_eq!(again.secret, otp.secret);
+        assert_eq!(again.algorithm, otp.algorithm);
+        assert_eq!(again.digits, otp.digits);
+        assert_eq!(again.kind, otp.kind);
+        assert_eq!(again.issuer, otp.issuer);
+        assert_eq!(again.account, otp.account);
+        assert_eq!(again.uri(), otp.uri());
+
+        let hotp = Otp::parse("otpauth://hotp/bob?secret=JBSWY3DPEHPK3PXP&counter=42").unwrap();
+        assert_eq!(hotp.kind, Kind::Hotp { counter: 42 });
+        assert_eq!(Otp::parse(&hotp.uri()).unwrap().kind, hotp.kind);
+    }
+
+    #[test]
+    fn uri_rejects_bad_values() {
+        // 2^32 + 6을 u32로 자르면 6이 되므로 거부되어야 함
+        for uri in [
+            "otpauth://totp/a?secret=JBSWY3DPEHPK3PXP&digits=4294967302",
+            "otpauth://totp/a?secret=JBSWY3DPEHPK3PXP&digits=5",
+            "otpauth://totp/a?secret=JBSWY3DPEHPK3PXP&period=0",
+            "otpauth://hotp/a?secret=JBSWY3DPEHPK3PXP",
+            "otpauth://hotp/a?secret=JBSWY3DPEHPK3PXP&counter=18446744073709551615",
+            "otpauth://totp/a?digits=6",
+            "otpauth://totp/a?secret=not-base32!",
+        ] {
+            assert!(Otp::parse(uri).is_err(), "{}", uri);
+        }
+    }
+}
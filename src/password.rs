@@ -0,0 +1,967 @@
+//! Password strength estimation in the style of zxcvbn, and password generation
+//!
+//! The estimate looks for the cheapest way to build the password out of the patterns
+//! an attacker tries first, common passwords and words, keyboard walks, sequences,
+//! repeats and dates, and counts the guesses needed to get there.
+
+use bip39::Language;
+use rand::Rng;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Only the first 100 characters are looked at, like zxcvbn
+pub const MAX_LENGTH: usize = 100;
+
+/// Guesses per character that fits no pattern
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+/// Fewest guesses a pattern of one character, or of more, can take
+const MIN_SINGLE_GUESSES: f64 = 10.0;
+const MIN_MULTI_GUESSES: f64 = 50.0;
+/// Extra guesses for every pattern after the first, so a long chain of patterns
+/// is not cheaper than it should be
+const MIN_GUESSES_PER_PATTERN: f64 = 10_000.0;
+
+/// Keys that can start a keyboard walk and the average number of neighbours of a key
+const KEYBOARD_STARTS: f64 = 94.0;
+const KEYBOARD_DEGREE: f64 = 4.6;
+
+/// Rows of a QWERTY keyboard, unshifted and shifted, each row half a key to the right
+/// of the one above
+const KEYBOARD_ROWS: [(&str, &str); 4] = [
+    ("`1234567890-=", "~!@#$%^&*()_+"),
+    ("qwertyuiop[]\\", "QWERTYUIOP{}|"),
+    ("asdfghjkl;'", "ASDFGHJKL:\""),
+    ("zxcvbnm,./", "ZXCVBNM<>?"),
+];
+
+/// Characters that stand in for letters
+const L33T: [(char, char); 15] = [
+    ('4', 'a'),
+    ('@', 'a'),
+    ('8', 'b'),
+    ('(', 'c'),
+    ('3', 'e'),
+    ('6', 'g'),
+    ('9', 'g'),
+    ('1', 'i'),
+    ('!', 'i'),
+    ('|', 'l'),
+    ('0', 'o'),
+    ('5', 's'),
+    ('$', 's'),
+    ('7', 't'),
+    ('2', 'z'),
+];
+
+/// The most used passwords in leaks, most used first
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456",
+    "password",
+    "123456789",
+    "12345678",
+    "12345",
+    "qwerty",
+    "1234567",
+    "111111",
+    "1234567890",
+    "123123",
+    "abc123",
+    "1234",
+    "password1",
+    "iloveyou",
+    "1q2w3e4r",
+    "000000",
+    "qwerty123",
+    "zaq12wsx",
+    "dragon",
+    "sunshine",
+    "princess",
+    "letmein",
+    "654321",
+    "monkey",
+    "27653",
+    "1qaz2wsx",
+    "123321",
+    "qwertyuiop",
+    "superman",
+    "asdfghjkl",
+    "football",
+    "baseball",
+    "welcome",
+    "admin",
+    "login",
+    "master",
+    "hello",
+    "freedom",
+    "whatever",
+    "qazwsx",
+    "trustno1",
+    "starwars",
+    "shadow",
+    "michael",
+    "jennifer",
+    "jordan",
+    "hunter",
+    "buster",
+    "soccer",
+    "harley",
+    "batman",
+    "andrew",
+    "tigger",
+    "charlie",
+    "robert",
+    "thomas",
+    "hockey",
+    "ranger",
+    "daniel",
+    "killer",
+    "george",
+    "computer",
+    "michelle",
+    "pepper",
+    "ginger",
+    "summer",
+    "ashley",
+    "nicole",
+    "chelsea",
+    "biteme",
+    "matthew",
+    "access",
+    "yankees",
+    "dallas",
+    "austin",
+    "thunder",
+    "taylor",
+    "matrix",
+    "mustang",
+    "secret",
+    "passw0rd",
+    "password123",
+    "loveme",
+    "flower",
+    "cookie",
+    "pokemon",
+    "naruto",
+    "lovely",
+    "angel",
+    "babygirl",
+    "butterfly",
+    "purple",
+    "orange",
+    "banana",
+    "chocolate",
+    "cheese",
+    "internet",
+    "samsung",
+    "google",
+    "maggie",
+    "jessica",
+    "hannah",
+    "anthony",
+    "joshua",
+    "family",
+    "forever",
+    "friends",
+    "blink182",
+    "mynoob",
+    "zxcvbnm",
+    "asdf",
+    "qwer",
+    "changeme",
+    "default",
+    "guest",
+    "root",
+    "test",
+    "test123",
+    "user",
+    "temp",
+    "pass",
+    "abcdef",
+    "abcd1234",
+    "aa123456",
+    "1111",
+    "7777777",
+    "666666",
+    "121212",
+    "159753",
+    "987654321",
+    "11111111",
+    "123qwe",
+    "qweasd",
+    "q1w2e3r4",
+    "a1b2c3",
+    "1q2w3e",
+    "asd123",
+    "love",
+    "god",
+    "sex",
+    "money",
+    "lucky",
+    "dragon1",
+    "monkey1",
+    "letmein1",
+    "welcome1",
+];
+
+/// A part of the password that follows a pattern
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// A common password or a word, `rank` is its place among the words tried
+    Dictionary {
+        word: String,
+        rank: usize,
+        reversed: bool,
+        l33t: bool,
+    },
+    /// Neighbouring keys on a QWERTY keyboard, changing direction `turns - 1` times
+    Keyboard { turns: usize },
+    /// Characters with evenly spaced codes like `abc`, `2468` or `zyx`
+    Sequence { ascending: bool },
+    /// The same block of characters over and over
+    Repeat { block: String, count: usize },
+    /// A date, or only a year when `month` is `None`
+    Date {
+        year: i32,
+        month: Option<u32>,
+        separator: Option<char>,
+    },
+    /// Characters that fit no pattern, guessed one at a time
+    Bruteforce,
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Dictionary {
+                word,
+                rank,
+                reversed,
+                l33t,
+            } => {
+                write!(f, "the word \"{}\" (rank {})", word, rank)?;
+                if *reversed {
+                    f.write_str(", reversed")?;
+                }
+                if *l33t {
+                    f.write_str(", with look-alike substitutions")?;
+                }
+                Ok(())
+            }
+            Pattern::Keyboard { turns: 1 } => f.write_str("a straight keyboard walk"),
+            Pattern::Keyboard { turns } => write!(f, "a keyboard walk with {} turns", turns),
+            Pattern::Sequence { ascending } => write!(
+                f,
+                "{} sequence",
+                if *ascending {
+                    "an ascending"
+                } else {
+                    "a descending"
+                }
+            ),
+            Pattern::Repeat { block, count } => {
+                write!(f, "\"{}\" repeated {} times", block, count)
+            }
+            Pattern::Date { year, month, .. } => match month {
+                Some(month) => write!(f, "a date in {}-{:02}", year, month),
+                None => write!(f, "the year {}", year),
+            },
+            Pattern::Bruteforce => f.write_str("random characters"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    /// Range in characters, not bytes
+    pub start: usize,
+    pub end: usize,
+    pub token: String,
+    pub pattern: Pattern,
+    pub guesses: f64,
+}
+
+/// The cheapest patterns covering a password, in order
+#[derive(Debug, Clone)]
+pub struct Estimate {
+    pub guesses: f64,
+    pub matches: Vec<Match>,
+}
+
+impl Estimate {
+    pub fn bits(&self) -> f64 {
+        self.guesses.log2()
+    }
+
+    /// From 0 (too guessable) to 4 (very unguessable), with zxcvbn's thresholds
+    pub fn score(&self) -> u8 {
+        [1e3, 1e6, 1e8, 1e10]
+            .iter()
+            .take_while(|&&threshold| self.guesses >= threshold + 5.0)
+            .count() as u8
+    }
+
+    /// Seconds to find the password on average, half of all the guesses
+    pub fn crack_seconds(&self, guesses_per_second: f64) -> f64 {
+        self.guesses / 2.0 / guesses_per_second
+    }
+}
+
+/// Estimate how many guesses an attacker needs to find the password
+pub fn estimate(password: &str) -> Estimate {
+    let chars: Vec<char> = password.chars().take(MAX_LENGTH).collect();
+    let matches = find_matches(&chars);
+    most_guessable_cover(&chars, matches)
+}
+
+fn token(chars: &[char]) -> String {
+    chars.iter().collect()
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k.min(n)).fold(1.0, |value, i| value * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Ways to change the case or form of some of the `changed + unchanged` characters
+/// when `changed` of them were changed, which zxcvbn counts as the sum of `C(n, i)`
+fn variations(changed: usize, unchanged: usize) -> f64 {
+    match (changed, unchanged) {
+        (0, _) => 1.0,
+        (_, 0) => 2.0,
+        _ => (1..=changed.min(unchanged))
+            .map(|i| binomial(changed + unchanged, i))
+            .sum(),
+    }
+}
+
+fn uppercase_variations(chars: &[char]) -> f64 {
+    let upper = chars.iter().filter(|c| c.is_uppercase()).count();
+    let lower = chars.iter().filter(|c| c.is_lowercase()).count();
+    let first_only = chars.first().is_some_and(|c| c.is_uppercase()) && upper == 1;
+    let last_only = chars.last().is_some_and(|c| c.is_uppercase()) && upper == 1;
+
+    // 첫 글자나 마지막 글자만 대문자인 경우는 흔하므로 2배만 셈
+    if upper == 0 {
+        1.0
+    } else if lower == 0 || first_only || last_only {
+        2.0
+    } else {
+        variations(upper, lower)
+    }
+}
+
+/// The rank of a common password or a word, words come after every common password
+fn dictionary_rank(word: &str) -> Option<usize> {
+    if let Some(index) = COMMON_PASSWORDS.iter().position(|&common| common == word) {
+        return Some(index + 1);
+    }
+
+    // BIP-39 목록은 빈도순이 아니라 알파벳순이므로 모든 단어를 같은 순위로 봄
+    Language::English
+        .find_word(word)
+        .map(|_| COMMON_PASSWORDS.len() + Language::English.word_list().len())
+}
+
+fn find_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = vec![];
+    dictionary_matches(chars, &mut matches);
+    keyboard_matches(chars, &mut matches);
+    sequence_matches(chars, &mut matches);
+    repeat_matches(chars, &mut matches);
+    date_matches(chars, &mut matches);
+
+    for found in &mut matches {
+        let minimum = if found.end - found.start == 1 {
+            MIN_SINGLE_GUESSES
+        } else {
+            MIN_MULTI_GUESSES
+        };
+        found.guesses = found.guesses.max(minimum);
+    }
+
+    matches
+}
+
+fn dictionary_matches(chars: &[char], matches: &mut Vec<Match>) {
+    let lower: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+    // 소문자로 바꿔도 길이가 같을 때만 위치가 맞음
+    if lower.len() != chars.len() {
+        return;
+    }
+
+    for start in 0..chars.len() {
+        for end in start + 3..=chars.len() {
+            let original = &chars[start..end];
+            let piece = &lower[start..end];
+            let unleeted: Vec<char> = piece
+                .iter()
+                .map(|&c| {
+                    L33T.iter()
+                        .find(|&&(from, _)| from == c)
+                        .map_or(c, |&(_, to)| to)
+                })
+                .collect();
+
+            let mut candidates = vec![(token(piece), false, false)];
+            let reversed: String = piece.iter().rev().collect();
+            if reversed != candidates[0].0 {
+                candidates.push((reversed, true, false));
+            }
+            if unleeted != piece {
+                candidates.push((token(&unleeted), false, true));
+            }
+
+            for (word, reversed, l33t) in candidates {
+                let Some(rank) = dictionary_rank(&word) else {
+                    continue;
+                };
+
+                let mut guesses = rank as f64 * uppercase_variations(original);
+                if reversed {
+                    guesses *= 2.0;
+                }
+                if l33t {
+                    let changed = piece.iter().zip(&unleeted).filter(|(a, b)| a != b).count();
+                    let unchanged = unleeted
+                        .iter()
+                        .filter(|&&c| L33T.iter().any(|&(_, to)| to == c))
+                        .count()
+                        - changed;
+                    guesses *= variations(changed, unchanged);
+                }
+
+                matches.push(Match {
+                    start,
+                    end,
+                    token: token(original),
+                    pattern: Pattern::Dictionary {
+                        word,
+                        rank,
+                        reversed,
+                        l33t,
+                    },
+                    guesses,
+                });
+            }
+        }
+    }
+}
+
+/// Position of a key on the keyboard in half keys, and whether it is shifted
+fn key_position(c: char) -> Option<(i32, i32, bool)> {
+    KEYBOARD_ROWS
+        .iter()
+        .enumerate()
+        .find_map(|(row, (unshifted, shifted))| {
+            let (column, shift) = match unshifted.chars().position(|key| key == c) {
+                Some(column) => (column, false),
+                None => (shifted.chars().position(|key| key == c)?, true),
+            };
+            Some((row as i32, 2 * column as i32 + row as i32, shift))
+        })
+}
+
+fn keyboard_matches(chars: &[char], matches: &mut Vec<Match>) {
+    let positions: Vec<_> = chars.iter().map(|&c| key_position(c)).collect();
+    // 이웃한 키 사이의 방향, 이웃이 아니면 None
+    let step = |from: usize| {
+        let (row, x, _) = positions[from]?;
+        let (next_row, next_x, _) = positions[from + 1]?;
+        let direction = (next_row - row, next_x - x);
+        matches!(direction, (0, -2 | 2) | (-1 | 1, -1 | 1)).then_some(direction)
+    };
+
+    let mut start = 0;
+    while start + 1 < chars.len() {
+        let mut end = start + 1;
+        let mut turns = 0;
+        let mut direction = None;
+        while end < chars.len() {
+            let Some(next) = step(end - 1) else {
+                break;
+            };
+            if direction != Some(next) {
+                turns += 1;
+                direction = Some(next);
+            }
+            end += 1;
+        }
+
+        if end - start >= 3 {
+            let length = end - start;
+            let mut guesses = 0.0;
+            for i in 2..=length {
+                for j in 1..=turns.min(i - 1) {
+                    guesses +=
+                        binomial(i - 1, j - 1) * KEYBOARD_STARTS * KEYBOARD_DEGREE.powi(j as i32);
+                }
+            }
+            let shifted = positions[start..end]
+                .iter()
+                .filter(|position| position.is_some_and(|(_, _, shift)| shift))
+                .count();
+            guesses *= variations(shifted, length - shifted);
+
+            matches.push(Match {
+                start,
+                end,
+                token: token(&chars[start..end]),
+                pattern: Pattern::Keyboard { turns },
+                guesses,
+            });
+            start = end - 1;
+        } else {
+            start += 1;
+        }
+    }
+}
+
+fn sequence_matches(chars: &[char], matches: &mut Vec<Match>) {
+    let class = |c: char| {
+        if c.is_ascii_lowercase() {
+            Some(0)
+        } else if c.is_ascii_uppercase() {
+            Some(1)
+        } else if c.is_ascii_digit() {
+            Some(2)
+        } else {
+            None
+        }
+    };
+    let delta = |i: usize| chars[i + 1] as i32 - chars[i] as i32;
+
+    let mut start = 0;
+    while start + 2 < chars.len() {
+        let first = delta(start);
+        let same_class =
+            |i: usize| class(chars[i]).is_some() && class(chars[i]) == class(chars[start]);
+        let mut end = start + 1;
+        while end < chars.len()
+            && same_class(end)
+            && delta(end - 1) == first
+            && (1..=5).contains(&first.abs())
+        {
+            end += 1;
+        }
+
+        if end - start >= 3 {
+            let head = chars[start];
+            let base = if "aAzZ019".contains(head) {
+                4.0
+            } else if head.is_ascii_digit() {
+                10.0
+            } else {
+                26.0
+            };
+            let ascending = first > 0;
+            let guesses = base * (end - start) as f64 * if ascending { 1.0 } else { 2.0 };
+
+            matches.push(Match {
+                start,
+                end,
+                token: token(&chars[start..end]),
+                pattern: Pattern::Sequence { ascending },
+                guesses,
+            });
+            start = end - 1;
+        } else {
+            start += 1;
+        }
+    }
+}
+
+fn repeat_matches(chars: &[char], matches: &mut Vec<Match>) {
+    for start in 0..chars.len() {
+        // 가장 길게 반복되는 블록, 길이가 같으면 짧은 블록을 고름
+        let mut best: Option<(usize, usize)> = None;
+        for size in 1..=(chars.len() - start) / 2 {
+            let block = &chars[start..start + size];
+            // 앞에서 이미 시작된 반복은 건너뜀
+            if start >= size && &chars[start - size..start] == block {
+                continue;
+            }
+            let count = chars[start..]
+                .chunks_exact(size)
+                .take_while(|&chunk| chunk == block)
+                .count();
+            let longer =
+                best.is_none_or(|(best_size, best_count)| count * size > best_size * best_count);
+            if count >= 2 && count * size >= 3 && longer {
+                best = Some((size, count));
+            }
+        }
+
+        let Some((size, count)) = best else {
+            continue;
+        };
+        let block = &chars[start..start + size];
+        let end = start + size * count;
+        matches.push(Match {
+            start,
+            end,
+            token: token(&chars[start..end]),
+            pattern: Pattern::Repeat {
+                block: token(block),
+                count,
+            },
+            guesses: most_guessable_cover(block, find_matches(block)).guesses * count as f64,
+        });
+    }
+}
+
+fn current_year() -> i32 {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    1970 + (seconds / 31_556_952) as i32
+}
+
+/// Years far from now are less likely, but every year at least 20 years around now
+/// is counted as equally likely
+fn year_guesses(year: i32) -> f64 {
+    (year - current_year()).abs().max(20) as f64
+}
+
+/// A day, month and year read in any common order, two digit years are taken as the
+/// closest year to 2000
+fn parse_date(parts: [&str; 3]) -> Option<(i32, u32)> {
+    let number = |part: &str| part.parse::<i32>().ok();
+    let year = |part: &str| {
+        let value = number(part)?;
+        match part.len() {
+            2 if value > 50 => Some(1900 + value),
+            2 => Some(2000 + value),
+            4 if (1000..=2050).contains(&value) => Some(value),
+            _ => None,
+        }
+    };
+    let day_month = |day: &str, month: &str| {
+        let (day, month) = (number(day)?, number(month)?);
+        ((1..=31).contains(&day) && (1..=12).contains(&month)).then_some(month as u32)
+    };
+
+    let [a, b, c] = parts;
+    // 연월일, 일월년, 월일년 순서로 시도
+    [
+        (year(a), day_month(c, b)),
+        (year(c), day_month(a, b)),
+        (year(c), day_month(b, a)),
+    ]
+    .into_iter()
+    .find_map(|(year, month)| Some((year?, month?)))
+}
+
+fn date_matches(chars: &[char], matches: &mut Vec<Match>) {
+    for start in 0..chars.len() {
+        for end in start + 4..=(start + 10).min(chars.len()) {
+            let piece = token(&chars[start..end]);
+            let separator = piece.chars().find(|c| !c.is_ascii_digit());
+
+            let date = match separator {
+                None => {
+                    let year = piece.parse::<i32>().unwrap();
+                    if piece.len() == 4 && (1900..=2050).contains(&year) {
+                        Some((year, None))
+                    } else {
+                        (1..piece.len() - 1)
+                            .flat_map(|i| (i + 1..piece.len()).map(move |j| (i, j)))
+                            .find_map(|(i, j)| parse_date([&piece[..i], &piece[i..j], &piece[j..]]))
+                            .map(|(year, month)| (year, Some(month)))
+                    }
+                }
+                Some(separator) if " -/._".contains(separator) => {
+                    let parts: Vec<&str> = piece.split(separator).collect();
+                    match parts[..] {
+                        [a, b, c]
+                            if [a, b, c].iter().all(|part| {
+                                !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())
+                            }) =>
+                        {
+                            parse_date([a, b, c]).map(|(year, month)| (year, Some(month)))
+                        }
+                        _ => None,
+                    }
+                }
+                Some(_) => None,
+            };
+
+            let Some((year, month)) = date else {
+                continue;
+            };
+            let mut guesses = year_guesses(year);
+            if month.is_some() {
+                guesses *= 365.0;
+            }
+            if separator.is_some() {
+                guesses *= 4.0;
+            }
+
+            matches.push(Match {
+                start,
+                end,
+                token: piece,
+                pattern: Pattern::Date {
+                    year,
+                    month,
+                    separator,
+                },
+                guesses,
+            });
+        }
+    }
+}
+
+fn factorial(n: usize) -> f64 {
+    (1..=n).map(|i| i as f64).product()
+}
+
+/// The sequence of matches and random characters covering the whole password with the
+/// fewest guesses, where `l` patterns cost `l! × Π guesses + 10000^(l - 1)`
+fn most_guessable_cover(chars: &[char], matches: Vec<Match>) -> Estimate {
+    let n = chars.len();
+    if n == 0 {
+        return Estimate {
+            guesses: 1.0,
+            matches: vec![],
+        };
+    }
+
+    // best[k][l]: 처음 k 글자를 l개의 패턴으로 덮는 가장 작은 추측 횟수의 곱과 마지막 패턴
+    let mut best: Vec<Vec<Option<(f64, Match)>>> = vec![vec![None; n + 1]; n + 1];
+    let mut by_end: Vec<Vec<Match>> = vec![vec![]; n + 1];
+    for found in matches {
+        by_end[found.end].push(found);
+    }
+
+    for end in 1..=n {
+        let mut candidates = by_end[end].clone();
+        for start in 0..end {
+            candidates.push(Match {
+                start,
+                end,
+                token: token(&chars[start..end]),
+                pattern: Pattern::Bruteforce,
+                guesses: BRUTEFORCE_CARDINALITY.powi((end - start) as i32).max(
+                    if end - start == 1 {
+                        MIN_SINGLE_GUESSES
+                    } else {
+                        MIN_MULTI_GUESSES
+                    },
+                ),
+            });
+        }
+
+        for candidate in candidates {
+            for length in 1..=end {
+                let before = if candidate.start == 0 {
+                    (length == 1).then_some(1.0)
+                } else {
+                    best[candidate.start][length - 1]
+                        .as_ref()
+                        .map(|(product, _)| *product)
+                };
+                let Some(before) = before else {
+                    continue;
+                };
+                // 무작위 문자 패턴 두 개를 잇는 것은 하나로 보는 것과 같으므로 제외
+                if candidate.pattern == Pattern::Bruteforce
+                    && best[candidate.start][length - 1]
+                        .as_ref()
+                        .is_some_and(|(_, previous)| previous.pattern == Pattern::Bruteforce)
+                {
+                    continue;
+                }
+
+                let product = before * candidate.guesses;
+                if best[end][length]
+                    .as_ref()
+                    .is_none_or(|(current, _)| product < *current)
+                {
+                    best[end][length] = Some((product, candidate.clone()));
+                }
+            }
+        }
+    }
+
+    let (length, guesses) = (1..=n)
+        .filter_map(|length| {
+            let (product, _) = best[n][length].as_ref()?;
+            let total =
+                factorial(length) * product + MIN_GUESSES_PER_PATTERN.powi(length as i32 - 1);
+            Some((length, total))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+
+    let mut cover = vec![];
+    let (mut end, mut length) = (n, length);
+    while length > 0 {
+        let (_, found) = best[end][length].clone().unwrap();
+        end = found.start;
+        length -= 1;
+        cover.push(found);
+    }
+    cover.reverse();
+
+    Estimate {
+        guesses,
+        matches: cover,
+    }
+}
+
+/// Plain language for how long something takes, like `3 hours` or `centuries`
+pub fn describe_seconds(seconds: f64) -> String {
+    const UNITS: [(&str, f64); 6] = [
+        ("second", 1.0),
+        ("minute", 60.0),
+        ("hour", 3600.0),
+        ("day", 86_400.0),
+        ("month", 2_629_746.0),
+        ("year", 31_556_952.0),
+    ];
+
+    if seconds < 1.0 {
+        return "less than a second".into();
+    }
+    if seconds >= 100.0 * 31_556_952.0 {
+        return "centuries".into();
+    }
+
+    let (unit, size) = UNITS
+        .iter()
+        .rev()
+        .find(|(_, size)| seconds >= *size)
+        .unwrap();
+    let count = (seconds / size).round() as u64;
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+pub const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+pub const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+pub const DIGITS: &str = "0123456789";
+pub const SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.<>/?~";
+
+/// A random password with at least one character from each of the `classes`
+///
+/// Passwords missing a class are thrown away and drawn again, so every password
+/// with all the classes is as likely as any other.
+pub fn generate(rng: &mut impl Rng, length: usize, classes: &[&str]) -> String {
+    let pool: Vec<char> = classes.iter().flat_map(|class| class.chars()).collect();
+    assert!(
+        !pool.is_empty() && length >= classes.len(),
+        "the password must have room for every class"
+    );
+
+    loop {
+        let password: String = (0..length)
+            .map(|_| pool[rng.gen_range(0..pool.len())])
+            .collect();
+        if classes
+            .iter()
+            .all(|class| password.chars().any(|c| class.contains(c)))
+        {
+            return password;
+        }
+    }
+}
+
+/// Random words from the BIP-39 English list, 11 bits each
+pub fn passphrase(rng: &mut impl Rng, words: usize, separator: &str) -> String {
+    let list = Language::English.word_list();
+    (0..words)
+        .map(|_| list[rng.gen_range(0..list.len())])
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn common_passwords_score_zero() {
+        for password in [
+            "password", "123456", "qwerty", "letmein1", "P@ssw0rd", "drowssap",
+        ] {
+            let estimate = estimate(password);
+            assert_eq!(estimate.score(), 0, "{}: {:?}", password, estimate.matches);
+        }
+    }
+
+    #[test]
+    fn random_passwords_score_four() {
+        for password in [
+            "q7#Vx!2mLp9@Rz4&Kd8*",
+            "correct-horse-battery-staple-zebra-quilt",
+        ] {
+            let estimate = estimate(password);
+            assert_eq!(estimate.score(), 4, "{}: {:?}", password, estimate.matches);
+        }
+    }
+
+    #[test]
+    fn matches_cover_the_password() {
+        let password = "qwertyabc1999aaaa`x";
+        let estimate = estimate(password);
+        let covered: String = estimate
+            .matches
+            .iter()
+            .map(|found| found.token.as_str())
+            .collect();
+
+        assert_eq!(covered, password);
+        assert!(estimate
+            .matches
+            .windows(2)
+            .all(|pair| pair[0].end == pair[1].start));
+    }
+
+    #[test]
+    fn long_and_empty_passwords() {
+        estimate("");
+        let long = "a".repeat(MAX_LENGTH * 3);
+        let covered: usize = estimate(&long)
+            .matches
+            .iter()
+            .map(|found| found.end - found.start)
+            .sum();
+        assert_eq!(covered, MAX_LENGTH);
+    }
+
+    #[test]
+    fn generated_passwords_have_every_class() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let classes = [LOWERCASE, UPPERCASE, DIGITS, SYMBOLS];
+
+        for length in [4, 5, 20, 64] {
+            for _ in 0..50 {
+                let password = generate(&mut rng, length, &classes);
+                assert_eq!(password.chars().count(), length);
+                for class in classes {
+                    assert!(password.chars().any(|c| class.contains(c)), "{}", password);
+                }
+                assert!(password
+                    .chars()
+                    .all(|c| classes.iter().any(|class| class.contains(c))));
+            }
+        }
+
+        let digits = generate(&mut rng, 12, &[DIGITS]);
+        assert!(digits.chars().all(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn passphrases_use_the_word_list() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let list = Language::English.word_list();
+
+        for words in [1, 6, 12] {
+            let phrase = passphrase(&mut rng, words, " ");
+            let parts: Vec<&str> = phrase.split(' ').collect();
+            assert_eq!(parts.len(), words);
+            assert!(parts.iter().all(|word| list.contains(word)), "{}", phrase);
+        }
+        assert_eq!(passphrase(&mut rng, 4, "-").matches('-').count(), 3);
+    }
+}
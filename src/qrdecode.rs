@@ -0,0 +1,1396 @@
+//! Locating and decoding QR codes in images
+//!
+//! Codes are found by their three finder patterns and sampled through a perspective
+//! transform, so rotated and slightly skewed photos work as well as screenshots.
+//! Only model 2 codes with normal versions are read, not micro QR codes.
+
+use image::GrayImage;
+use qrcode::canvas::is_functional;
+use qrcode::{EcLevel, Version};
+
+/// A decoded QR code
+#[derive(Debug, Clone)]
+pub struct Decoded {
+    pub text: String,
+    pub version: i16,
+    pub level: EcLevel,
+}
+
+/// Decode every QR code that can be found in the image
+///
+/// Dark codes on a light background are tried first, then light codes on a dark one.
+pub fn decode(image: &GrayImage) -> Vec<Decoded> {
+    let global = Bitmap::global(image);
+    let local = Bitmap::local(image);
+
+    for bitmap in [&global, &local, &global.inverted(), &local.inverted()] {
+        let decoded = decode_bitmap(bitmap);
+        if !decoded.is_empty() {
+            return decoded;
+        }
+    }
+
+    vec![]
+}
+
+/// Dark and light pixels of an image
+struct Bitmap {
+    width: i64,
+    height: i64,
+    dark: Vec<bool>,
+}
+
+impl Bitmap {
+    fn contains(&self, x: i64, y: i64) -> bool {
+        (0..self.width).contains(&x) && (0..self.height).contains(&y)
+    }
+
+    /// Whether the pixel is dark, anything outside the image is light
+    fn get(&self, x: i64, y: i64) -> bool {
+        self.contains(x, y) && self.dark[(y * self.width + x) as usize]
+    }
+
+    fn at(&self, x: f64, y: f64) -> bool {
+        self.get(x.floor() as i64, y.floor() as i64)
+    }
+
+    /// One threshold for the whole image, which suits screenshots
+    fn global(image: &GrayImage) -> Self {
+        let threshold = otsu(image);
+
+        Bitmap {
+            width: image.width() as i64,
+            height: image.height() as i64,
+            dark: image.pixels().map(|pixel| pixel[0] < threshold).collect(),
+        }
+    }
+
+    /// A threshold from the mean of the surroundings, which suits uneven lighting
+    fn local(image: &GrayImage) -> Self {
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let threshold = otsu(image) as f64;
+        let radius = (width.max(height) / 16).max(8);
+
+        // 합계와 제곱합의 누적 테이블로 창 평균과 분산을 바로 계산
+        let mut sums = vec![0u64; (width + 1) * (height + 1)];
+        let mut squares = vec![0u64; (width + 1) * (height + 1)];
+        for y in 0..height {
+            let (mut row_sum, mut row_square) = (0u64, 0u64);
+            for x in 0..width {
+                let value = image.get_pixel(x as u32, y as u32)[0] as u64;
+                row_sum += value;
+                row_square += value * value;
+                let index = (y + 1) * (width + 1) + x + 1;
+                sums[index] = sums[index - width - 1] + row_sum;
+                squares[index] = squares[index - width - 1] + row_square;
+            }
+        }
+
+        let area = |table: &[u64], left: usize, top: usize, right: usize, bottom: usize| {
+            (table[bottom * (width + 1) + right] + table[top * (width + 1) + left])
+                - (table[top * (width + 1) + right] + table[bottom * (width + 1) + left])
+        };
+
+        let mut dark = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let (top, bottom) = (y.saturating_sub(radius), (y + radius + 1).min(height));
+            for x in 0..width {
+                let (left, right) = (x.saturating_sub(radius), (x + radius + 1).min(width));
+                let count = ((right - left) * (bottom - top)) as f64;
+                let mean = area(&sums, left, top, right, bottom) as f64 / count;
+                let variance =
+                    area(&squares, left, top, right, bottom) as f64 / count - mean * mean;
+
+                let value = image.get_pixel(x as u32, y as u32)[0] as f64;
+                // 밝기가 거의 고른 곳은 평균이 의미가 없으므로 전체 기준값 사용
+                dark.push(if variance < 144.0 {
+                    value < threshold
+                } else {
+                    value < mean
+                });
+            }
+        }
+
+        Bitmap {
+            width: width as i64,
+            height: height as i64,
+            dark,
+        }
+    }
+
+    fn inverted(&self) -> Self {
+        Bitmap {
+            width: self.width,
+            height: self.height,
+            dark: self.dark.iter().map(|dark| !dark).collect(),
+        }
+    }
+}
+
+/// The threshold that best separates the histogram into two classes
+fn otsu(image: &GrayImage) -> u8 {
+    let mut histogram = [0u64; 256];
+    for pixel in image.pixels() {
+        histogram[pixel[0] as usize] += 1;
+    }
+
+    let total: u64 = histogram.iter().sum();
+    let total_sum: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(value, &count)| value as f64 * count as f64)
+        .sum();
+
+    let (mut best, mut best_variance) = (128, -1.0);
+    let (mut weight, mut sum) = (0u64, 0.0);
+    for (value, &count) in histogram.iter().enumerate() {
+        weight += count;
+        sum += value as f64 * count as f64;
+        if weight == 0 || weight == total {
+            continue;
+        }
+
+        let (below, above) = (weight as f64, (total - weight) as f64);
+        let difference = sum / below - (total_sum - sum) / above;
+        let variance = below * above * difference * difference;
+        if variance > best_variance {
+            best_variance = variance;
+            best = value + 1;
+        }
+    }
+
+    best.min(255) as u8
+}
+
+/// The center of a finder pattern in pixels
+#[derive(Debug, Clone, Copy)]
+struct Finder {
+    x: f64,
+    y: f64,
+    module: f64,
+    count: u32,
+}
+
+/// Whether five runs are in the 1:1:3:1:1 ratio of a finder pattern
+fn finder_ratio(runs: &[u32; 5]) -> bool {
+    let total: u32 = runs.iter().sum();
+    if total < 7 || runs.contains(&0) {
+        return false;
+    }
+
+    let module = total as f64 / 7.0;
+    runs.iter().enumerate().all(|(index, &run)| {
+        let expected = if index == 2 { 3.0 } else { 1.0 };
+        (run as f64 - module * expected).abs() < module * expected / 2.0
+    })
+}
+
+/// Lengths of the dark, light and dark runs from a point, up to `limit` pixels each
+fn walk(bitmap: &Bitmap, x: i64, y: i64, dx: i64, dy: i64, limit: u32) -> Option<[u32; 3]> {
+    let mut counts = [0; 3];
+    let (mut x, mut y) = (x, y);
+
+    for (index, dark) in [true, false, true].into_iter().enumerate() {
+        while bitmap.contains(x, y) && bitmap.get(x, y) == dark && counts[index] <= limit {
+            counts[index] += 1;
+            x += dx;
+            y += dy;
+        }
+        // 중심에서 시작한 반대 방향은 첫 구간이 비어 있을 수 있음
+        if (counts[index] == 0 && index > 0) || counts[index] > limit {
+            return None;
+        }
+    }
+
+    Some(counts)
+}
+
+/// The runs along a line through a dark pixel, and the center of the middle run
+///
+/// The center is measured in pixels along the line from the edge of the start pixel.
+fn cross_check(
+    bitmap: &Bitmap,
+    x: i64,
+    y: i64,
+    dx: i64,
+    dy: i64,
+    limit: u32,
+) -> Option<([u32; 5], f64)> {
+    if !bitmap.get(x, y) {
+        return None;
+    }
+
+    let forward = walk(bitmap, x, y, dx, dy, limit)?;
+    let backward = walk(bitmap, x - dx, y - dy, -dx, -dy, limit)?;
+    let runs = [
+        backward[2],
+        backward[1],
+        backward[0] + forward[0],
+        forward[1],
+        forward[2],
+    ];
+
+    Some((runs, (forward[0] as f64 - backward[0] as f64) / 2.0))
+}
+
+/// Most finder candidates kept while scanning, each new line match is compared to all of them
+const MAX_CANDIDATES: usize = 512;
+
+fn find_finders(bitmap: &Bitmap) -> Vec<Finder> {
+    let mut finders: Vec<Finder> = vec![];
+
+    for y in 0..bitmap.height {
+        let mut runs: Vec<(i64, u32, bool)> = vec![];
+        for x in 0..bitmap.width {
+            let dark = bitmap.get(x, y);
+            match runs.last_mut() {
+                Some(run) if run.2 == dark => run.1 += 1,
+                _ => runs.push((x, 1, dark)),
+            }
+        }
+
+        for window in runs.windows(5) {
+            if !window[0].2 {
+                continue;
+            }
+            let lengths = [
+                window[0].1,
+                window[1].1,
+                window[2].1,
+                window[3].1,
+                window[4].1,
+            ];
+            if !finder_ratio(&lengths) {
+                continue;
+            }
+
+            let total: u32 = lengths.iter().sum();
+            let x = window[2].0 as f64 + window[2].1 as f64 / 2.0;
+
+            // 세로 방향으로 다시 확인해서 중심의 y를 구하고, 그 줄에서 x를 다시 구함
+            let Some((vertical, offset)) = cross_check(bitmap, x as i64, y, 0, 1, total) else {
+                continue;
+            };
+            let vertical_total: u32 = vertical.iter().sum();
+            if !finder_ratio(&vertical) || vertical_total.abs_diff(total) * 5 >= total * 2 {
+                continue;
+            }
+            let center_y = y as f64 + offset;
+
+            let Some((horizontal, offset)) =
+                cross_check(bitmap, x as i64, center_y as i64, 1, 0, total * 2)
+            else {
+                continue;
+            };
+            if !finder_ratio(&horizontal) {
+                continue;
+            }
+            let center_x = (x as i64) as f64 + offset;
+            let module = (horizontal.iter().sum::<u32>() + vertical_total) as f64 / 14.0;
+
+            match finders.iter_mut().find(|finder| {
+                (finder.x - center_x).abs() <= finder.module * 2.0
+                    && (finder.y - center_y).abs() <= finder.module * 2.0
+                    && module / finder.module < 2.0
+                    && finder.module / module < 2.0
+            }) {
+                Some(finder) => {
+                    let count = finder.count as f64;
+                    finder.x = (finder.x * count + center_x) / (count + 1.0);
+                    finder.y = (finder.y * count + center_y) / (count + 1.0);
+                    finder.module = (finder.module * count + module) / (count + 1.0);
+                    finder.count += 1;
+                }
+                None => {
+                    // 잡음이 많은 이미지에서 후보를 찾는 비용이 제곱으로 늘지 않도록
+                    // 한 번만 보인 후보부터 버리고, 그래도 가득 차면 새 후보를 받지 않음
+                    if finders.len() >= MAX_CANDIDATES {
+                        finders.retain(|finder| finder.count >= 2);
+                    }
+                    if finders.len() < MAX_CANDIDATES {
+                        finders.push(Finder {
+                            x: center_x,
+                            y: center_y,
+                            module,
+                            count: 1,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    finders.retain(|finder| finder.count >= 2);
+    finders.sort_by_key(|finder| std::cmp::Reverse(finder.count));
+    finders.truncate(16);
+
+    finders
+}
+
+/// Sets of three finders that form a right angle, best first, as top left, top right, bottom left
+fn find_corners(finders: &[Finder]) -> Vec<[usize; 3]> {
+    let mut corners = vec![];
+
+    for a in 0..finders.len() {
+        for b in a + 1..finders.len() {
+            for c in b + 1..finders.len() {
+                let modules = [finders[a].module, finders[b].module, finders[c].module];
+                let smallest = modules.iter().copied().fold(f64::INFINITY, f64::min);
+                let largest = modules.iter().copied().fold(0.0, f64::max);
+                if largest / smallest > 1.6 {
+                    continue;
+                }
+
+                // 직각에 가장 가까운 꼭짓점을 왼쪽 위로 정함
+                let mut best: Option<(f64, [usize; 3])> = None;
+                for [corner, first, second] in [[a, b, c], [b, a, c], [c, a, b]] {
+                    let origin = finders[corner];
+                    let (ux, uy) = (finders[first].x - origin.x, finders[first].y - origin.y);
+                    let (vx, vy) = (finders[second].x - origin.x, finders[second].y - origin.y);
+                    let (u, v) = (ux.hypot(uy), vx.hypot(vy));
+                    if u < origin.module * 10.0 || v < origin.module * 10.0 {
+                        continue;
+                    }
+
+                    let cosine = (ux * vx + uy * vy) / (u * v);
+                    let score = cosine.abs() + (u - v).abs() / u.max(v);
+                    if cosine.abs() > 0.35 || best.is_some_and(|(best, _)| best <= score) {
+                        continue;
+                    }
+
+                    // y가 아래로 커지므로 오른쪽 위에서 왼쪽 아래로 도는 방향이 양수
+                    let order = if ux * vy - uy * vx > 0.0 {
+                        [corner, first, second]
+                    } else {
+                        [corner, second, first]
+                    };
+                    best = Some((score, order));
+                }
+
+                if let Some(best) = best {
+                    corners.push(best);
+                }
+            }
+        }
+    }
+
+    corners.sort_by(|a, b| a.0.total_cmp(&b.0));
+    corners.into_iter().map(|(_, corner)| corner).collect()
+}
+
+fn decode_bitmap(bitmap: &Bitmap) -> Vec<Decoded> {
+    let finders = find_finders(bitmap);
+    let mut used = vec![false; finders.len()];
+    let mut decoded = vec![];
+
+    for corner in find_corners(&finders).into_iter().take(40) {
+        if corner.iter().any(|&index| used[index]) {
+            continue;
+        }
+
+        let [top_left, top_right, bottom_left] = corner.map(|index| finders[index]);
+        if let Some(code) = decode_at(bitmap, top_left, top_right, bottom_left) {
+            for index in corner {
+                used[index] = true;
+            }
+            decoded.push(code);
+        }
+    }
+
+    decoded
+}
+
+/// Distance from the center of a finder to the outside of its outer ring along a direction
+fn ring_distance(bitmap: &Bitmap, finder: Finder, dx: f64, dy: f64) -> Option<f64> {
+    let mut ring = 0;
+    let mut distance = 0.0;
+
+    while distance < finder.module * 8.0 {
+        let dark = bitmap.at(finder.x + dx * distance, finder.y + dy * distance);
+        match (ring, dark) {
+            (0, false) | (1, true) => ring += 1,
+            (2, false) => return Some(distance),
+            _ => {}
+        }
+        distance += 0.5;
+    }
+
+    None
+}
+
+/// The size of a module along the line between two finders
+fn module_between(bitmap: &Bitmap, from: Finder, to: Finder) -> f64 {
+    let length = (to.x - from.x).hypot(to.y - from.y);
+    let (dx, dy) = ((to.x - from.x) / length, (to.y - from.y) / length);
+
+    let distances = [
+        ring_distance(bitmap, from, dx, dy),
+        ring_distance(bitmap, from, -dx, -dy),
+        ring_distance(bitmap, to, -dx, -dy),
+        ring_distance(bitmap, to, dx, dy),
+    ];
+
+    match distances {
+        [Some(a), Some(b), Some(c), Some(d)] => (a + b + c + d) / 14.0,
+        _ => (from.module + to.module) / 2.0,
+    }
+}
+
+fn decode_at(
+    bitmap: &Bitmap,
+    top_left: Finder,
+    top_right: Finder,
+    bottom_left: Finder,
+) -> Option<Decoded> {
+    let distance = |a: Finder, b: Finder| (b.x - a.x).hypot(b.y - a.y);
+    let modules = (distance(top_left, top_right) / module_between(bitmap, top_left, top_right)
+        + distance(top_left, bottom_left) / module_between(bitmap, top_left, bottom_left))
+        / 2.0;
+    let estimate = (((modules + 7.0 - 17.0) / 4.0).round() as i16).clamp(1, 40);
+
+    // 크기 추정은 한두 단계 어긋날 수 있으므로 주변 버전도 시도
+    let mut tried = vec![];
+    for mut version in [estimate, estimate - 1, estimate + 1] {
+        if !(1..=40).contains(&version) || tried.contains(&version) {
+            continue;
+        }
+        tried.push(version);
+
+        let mut grid = sample(bitmap, top_left, top_right, bottom_left, version)?;
+        if version >= 7 {
+            match read_version(&grid) {
+                Some(read) if read != version => {
+                    if tried.contains(&read) {
+                        continue;
+                    }
+                    tried.push(read);
+                    version = read;
+                    grid = sample(bitmap, top_left, top_right, bottom_left, version)?;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(decoded) = decode_grid(&grid, version) {
+            return Some(decoded);
+        }
+    }
+
+    None
+}
+
+/// Maps module coordinates to pixel coordinates
+struct Perspective([f64; 8]);
+
+impl Perspective {
+    /// The transform through four pairs of module and pixel coordinates
+    fn new(pairs: [((f64, f64), (f64, f64)); 4]) -> Option<Self> {
+        let mut rows = [[0.0; 9]; 8];
+        for (index, ((u, v), (x, y))) in pairs.into_iter().enumerate() {
+            rows[index * 2] = [u, v, 1.0, 0.0, 0.0, 0.0, -u * x, -v * x, x];
+            rows[index * 2 + 1] = [0.0, 0.0, 0.0, u, v, 1.0, -u * y, -v * y, y];
+        }
+
+        // 가우스-조르단 소거
+        for column in 0..8 {
+            let pivot = (column..8)
+                .max_by(|&a, &b| rows[a][column].abs().total_cmp(&rows[b][column].abs()))?;
+            if rows[pivot][column].abs() < 1e-9 {
+                return None;
+            }
+            rows.swap(column, pivot);
+
+            let pivot_row = rows[column];
+            for (index, row) in rows.iter_mut().enumerate() {
+                if index == column {
+                    continue;
+                }
+                let factor = row[column] / pivot_row[column];
+                for (value, pivot_value) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+
+        Some(Perspective(std::array::from_fn(|index| {
+            rows[index][8] / rows[index][index]
+        })))
+    }
+
+    fn apply(&self, u: f64, v: f64) -> (f64, f64) {
+        let h = &self.0;
+        let w = h[6] * u + h[7] * v + 1.0;
+        (
+            (h[0] * u + h[1] * v + h[2]) / w,
+            (h[3] * u + h[4] * v + h[5]) / w,
+        )
+    }
+}
+
+/// The center of the bottom right alignment pattern closest to where it should be
+///
+/// `step_x` and `step_y` are the size of a module along each axis near the pattern.
+fn find_alignment(
+    bitmap: &Bitmap,
+    expected: (f64, f64),
+    step_x: (f64, f64),
+    step_y: (f64, f64),
+) -> Option<(f64, f64)> {
+    let module = step_x.0.hypot(step_x.1).max(step_y.0.hypot(step_y.1));
+    let stride = (module / 3.0).max(1.0);
+
+    let score = |x: f64, y: f64| {
+        let mut matches = 0;
+        for j in -2..=2i32 {
+            for i in -2..=2i32 {
+                let dark = i.abs().max(j.abs()) != 1;
+                let sample_x = x + step_x.0 * i as f64 + step_y.0 * j as f64;
+                let sample_y = y + step_x.1 * i as f64 + step_y.1 * j as f64;
+                if bitmap.at(sample_x, sample_y) == dark {
+                    matches += 1;
+                }
+            }
+        }
+        matches
+    };
+
+    // 원근이 심하면 예상 위치에서 멀어질 수 있으므로 범위를 넓혀 가며 찾음
+    for radius in [4.0, 8.0, 16.0] {
+        let steps = (radius * module / stride).ceil() as i64;
+        let (mut best, mut found) = (0, vec![]);
+        for j in -steps..=steps {
+            for i in -steps..=steps {
+                let (x, y) = (
+                    expected.0 + i as f64 * stride,
+                    expected.1 + j as f64 * stride,
+                );
+                let matches = score(x, y);
+                if matches > best {
+                    best = matches;
+                    found.clear();
+                }
+                if matches == best {
+                    found.push((x, y));
+                }
+            }
+        }
+        if best < 23 {
+            continue;
+        }
+
+        // 가장 가까운 후보 주변의 위치들을 평균해서 중심으로 사용
+        let distance = |&(x, y): &(f64, f64)| (x - expected.0).hypot(y - expected.1);
+        let closest = *found
+            .iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))?;
+        let near: Vec<_> = found
+            .into_iter()
+            .filter(|&(x, y)| (x - closest.0).hypot(y - closest.1) <= module)
+            .collect();
+        let count = near.len() as f64;
+
+        return Some((
+            near.iter().map(|point| point.0).sum::<f64>() / count,
+            near.iter().map(|point| point.1).sum::<f64>() / count,
+        ));
+    }
+
+    None
+}
+
+/// Modules of a code, row by row
+struct Grid {
+    size: i64,
+    modules: Vec<bool>,
+}
+
+impl Grid {
+    /// Whether the module is dark, negative coordinates count from the end
+    fn get(&self, x: i64, y: i64) -> bool {
+        let x = if x < 0 { x + self.size } else { x };
+        let y = if y < 0 { y + self.size } else { y };
+        self.modules[(y * self.size + x) as usize]
+    }
+}
+
+fn sample(
+    bitmap: &Bitmap,
+    top_left: Finder,
+    top_right: Finder,
+    bottom_left: Finder,
+    version: i16,
+) -> Option<Grid> {
+    let size = 17 + 4 * version as i64;
+    let span = (size - 7) as f64;
+    let step_x = (
+        (top_right.x - top_left.x) / span,
+        (top_right.y - top_left.y) / span,
+    );
+    let step_y = (
+        (bottom_left.x - top_left.x) / span,
+        (bottom_left.y - top_left.y) / span,
+    );
+
+    let far = size as f64 - 3.5;
+    // 정렬 패턴이 없으면 평행사변형의 네 번째 꼭짓점을 사용
+    let mut fourth = (
+        (far, far),
+        (
+            top_right.x + bottom_left.x - top_left.x,
+            top_right.y + bottom_left.y - top_left.y,
+        ),
+    );
+    if version >= 2 {
+        // 원근 변환의 분모는 모듈 크기에 반비례하므로 세 파인더의 모듈 크기로 추정
+        let weights = [
+            1.0 / top_left.module,
+            1.0 / top_right.module,
+            1.0 / bottom_left.module,
+        ];
+        let t = (span - 3.0) / span;
+        let weight = weights[0] + t * (weights[1] - weights[0]) + t * (weights[2] - weights[0]);
+        let project = |a: f64, b: f64, c: f64| {
+            (a * weights[0]
+                + t * (b * weights[1] - a * weights[0])
+                + t * (c * weights[2] - a * weights[0]))
+                / weight
+        };
+
+        if weight > 0.0 {
+            let expected = (
+                project(top_left.x, top_right.x, bottom_left.x),
+                project(top_left.y, top_right.y, bottom_left.y),
+            );
+            let scale = weights[0] / weight;
+            let step_x = (step_x.0 * scale, step_x.1 * scale);
+            let step_y = (step_y.0 * scale, step_y.1 * scale);
+            if let Some(found) = find_alignment(bitmap, expected, step_x, step_y) {
+                fourth = ((far - 3.0, far - 3.0), found);
+            }
+        }
+    }
+
+    let perspective = Perspective::new([
+        ((3.5, 3.5), (top_left.x, top_left.y)),
+        ((far, 3.5), (top_right.x, top_right.y)),
+        ((3.5, far), (bottom_left.x, bottom_left.y)),
+        fourth,
+    ])?;
+
+    let mut modules = Vec::with_capacity((size * size) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let (px, py) = perspective.apply(x as f64 + 0.5, y as f64 + 0.5);
+            modules.push(bitmap.at(px, py));
+        }
+    }
+
+    Some(Grid { size, modules })
+}
+
+/// Data bits followed by their BCH error correction bits
+fn bch_code(data: u32, data_bits: u32, generator: u32) -> u32 {
+    let degree = 31 - generator.leading_zeros();
+    let mut remainder = data << degree;
+    for shift in (0..data_bits).rev() {
+        if remainder & (1 << (shift + degree)) != 0 {
+            remainder ^= generator << shift;
+        }
+    }
+
+    (data << degree) | remainder
+}
+
+/// The data of the valid code closest to any of the copies, if close enough to correct
+fn closest_code(copies: &[u32], codes: impl Iterator<Item = (u32, u32)>) -> Option<u32> {
+    codes
+        .flat_map(|(data, code)| {
+            copies
+                .iter()
+                .map(move |copy| ((copy ^ code).count_ones(), data))
+        })
+        .min()
+        .filter(|(distance, _)| *distance <= 3)
+        .map(|(_, data)| data)
+}
+
+fn read_bits(grid: &Grid, positions: impl Iterator<Item = (i64, i64)>) -> u32 {
+    positions.fold(0, |bits, (x, y)| bits << 1 | grid.get(x, y) as u32)
+}
+
+/// The error correction level as an index into the tables, and the mask
+fn read_format(grid: &Grid) -> Option<(usize, u8)> {
+    let main = (0..=5)
+        .map(|x| (x, 8))
+        .chain([(7, 8), (8, 8), (8, 7)])
+        .chain((0..=5).rev().map(|y| (8, y)));
+    let side = (1..=7)
+        .map(|y| (8, -y))
+        .chain((1..=8).rev().map(|x| (-x, 8)));
+
+    let copies = [read_bits(grid, main), read_bits(grid, side)];
+    let codes = (0..32).map(|data| (data, bch_code(data, 5, 0x537) ^ 0x5412));
+    let data = closest_code(&copies, codes)?;
+
+    // 형식 정보의 수준 비트는 L, M, Q, H 순서가 아님
+    let level = [1, 0, 3, 2][(data >> 3) as usize];
+    Some((level, (data & 7) as u8))
+}
+
+/// The version from the version information, only present from version 7
+fn read_version(grid: &Grid) -> Option<i16> {
+    let bottom_left = (0..6).rev().flat_map(|x| [-9, -10, -11].map(|y| (x, y)));
+    let top_right = (0..6).rev().flat_map(|y| [-9, -10, -11].map(|x| (x, y)));
+
+    let copies = [read_bits(grid, bottom_left), read_bits(grid, top_right)];
+    let codes = (7..=40).map(|version| (version, bch_code(version, 6, 0x1f25)));
+
+    closest_code(&copies, codes).map(|version| version as i16)
+}
+
+fn masked(mask: u8, x: i64, y: i64) -> bool {
+    let (row, column) = (y, x);
+    match mask {
+        0 => (row + column) % 2 == 0,
+        1 => row % 2 == 0,
+        2 => column % 3 == 0,
+        3 => (row + column) % 3 == 0,
+        4 => (row / 2 + column / 3) % 2 == 0,
+        5 => (row * column) % 2 + (row * column) % 3 == 0,
+        6 => ((row * column) % 2 + (row * column) % 3) % 2 == 0,
+        _ => ((row + column) % 2 + (row * column) % 3) % 2 == 0,
+    }
+}
+
+/// Whether a module holds a pattern or format or version information rather than data
+fn is_reserved(version: i16, size: i64, x: i64, y: i64) -> bool {
+    let version_area = |a: i64, b: i64| a < 6 && (size - 11..size - 8).contains(&b);
+
+    is_functional(Version::Normal(version), size as i16, x as i16, y as i16)
+        || (version >= 7 && (version_area(x, y) || version_area(y, x)))
+}
+
+/// The unmasked codewords in the zigzag order they are placed in
+fn read_codewords(grid: &Grid, version: i16, mask: u8) -> Vec<u8> {
+    let size = grid.size;
+    let mut bits = vec![];
+    let mut upward = true;
+    let mut column = size - 1;
+
+    while column > 0 {
+        // 세로 타이밍 패턴이 있는 열은 건너뜀
+        if column == 6 {
+            column -= 1;
+        }
+        for step in 0..size {
+            let y = if upward { size - 1 - step } else { step };
+            for x in [column, column - 1] {
+                if !is_reserved(version, size, x, y) {
+                    bits.push(grid.get(x, y) ^ masked(mask, x, y));
+                }
+            }
+        }
+        upward = !upward;
+        column -= 2;
+    }
+
+    bits.chunks_exact(8)
+        .map(|byte| byte.iter().fold(0, |value, &bit| value << 1 | bit as u8))
+        .collect()
+}
+
+/// Error correction codewords per block for every version, in the order L, M, Q, H
+#[rustfmt::skip]
+const EC_CODEWORDS: [[usize; 4]; 40] = [
+    [7, 10, 13, 17], [10, 16, 22, 28], [15, 26, 18, 22], [20, 18, 26, 16], [26, 24, 18, 22],
+    [18, 16, 24, 28], [20, 18, 18, 26], [24, 22, 22, 26], [30, 22, 20, 24], [18, 26, 24, 28],
+    [20, 30, 28, 24], [24, 22, 26, 28], [26, 22, 24, 22], [30, 24, 20, 24], [22, 24, 30, 24],
+    [24, 28, 24, 30], [28, 28, 28, 28], [30, 26, 28, 28], [28, 26, 26, 26], [28, 26, 30, 28],
+    [28, 26, 28, 30], [28, 28, 30, 24], [30, 28, 30, 30], [30, 28, 30, 30], [26, 28, 30, 30],
+    [28, 28, 28, 30], [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30],
+    [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30],
+    [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30], [30, 28, 30, 30],
+];
+
+/// Data blocks for every version, in the order L, M, Q, H
+///
+/// Each entry is the number of short blocks, their data codewords,
+/// and the number of blocks with one more data codeword.
+#[rustfmt::skip]
+const BLOCKS: [[(usize, usize, usize); 4]; 40] = [
+    [(1, 19, 0), (1, 16, 0), (1, 13, 0), (1, 9, 0)],
+    [(1, 34, 0), (1, 28, 0), (1, 22, 0), (1, 16, 0)],
+    [(1, 55, 0), (1, 44, 0), (2, 17, 0), (2, 13, 0)],
+    [(1, 80, 0), (2, 32, 0), (2, 24, 0), (4, 9, 0)],
+    [(1, 108, 0), (2, 43, 0), (2, 15, 2), (2, 11, 2)],
+    [(2, 68, 0), (4, 27, 0), (4, 19, 0), (4, 15, 0)],
+    [(2, 78, 0), (4, 31, 0), (2, 14, 4), (4, 13, 1)],
+    [(2, 97, 0), (2, 38, 2), (4, 18, 2), (4, 14, 2)],
+    [(2, 116, 0), (3, 36, 2), (4, 16, 4), (4, 12, 4)],
+    [(2, 68, 2), (4, 43, 1), (6, 19, 2), (6, 15, 2)],
+    [(4, 81, 0), (1, 50, 4), (4, 22, 4), (3, 12, 8)],
+    [(2, 92, 2), (6, 36, 2), (4, 20, 6), (7, 14, 4)],
+    [(4, 107, 0), (8, 37, 1), (8, 20, 4), (12, 11, 4)],
+    [(3, 115, 1), (4, 40, 5), (11, 16, 5), (11, 12, 5)],
+    [(5, 87, 1), (5, 41, 5), (5, 24, 7), (11, 12, 7)],
+    [(5, 98, 1), (7, 45, 3), (15, 19, 2), (3, 15, 13)],
+    [(1, 107, 5), (10, 46, 1), (1, 22, 15), (2, 14, 17)],
+    [(5, 120, 1), (9, 43, 4), (17, 22, 1), (2, 14, 19)],
+    [(3, 113, 4), (3, 44, 11), (17, 21, 4), (9, 13, 16)],
+    [(3, 107, 5), (3, 41, 13), (15, 24, 5), (15, 15, 10)],
+    [(4, 116, 4), (17, 42, 0), (17, 22, 6), (19, 16, 6)],
+    [(2, 111, 7), (17, 46, 0), (7, 24, 16), (34, 13, 0)],
+    [(4, 121, 5), (4, 47, 14), (11, 24, 14), (16, 15, 14)],
+    [(6, 117, 4), (6, 45, 14), (11, 24, 16), (30, 16, 2)],
+    [(8, 106, 4), (8, 47, 13), (7, 24, 22), (22, 15, 13)],
+    [(10, 114, 2), (19, 46, 4), (28, 22, 6), (33, 16, 4)],
+    [(8, 122, 4), (22, 45, 3), (8, 23, 26), (12, 15, 28)],
+    [(3, 117, 10), (3, 45, 23), (4, 24, 31), (11, 15, 31)],
+    [(7, 116, 7), (21, 45, 7), (1, 23, 37), (19, 15, 26)],
+    [(5, 115, 10), (19, 47, 10), (15, 24, 25), (23, 15, 25)],
+    [(13, 115, 3), (2, 46, 29), (42, 24, 1), (23, 15, 28)],
+    [(17, 115, 0), (10, 46, 23), (10, 24, 35), (19, 15, 35)],
+    [(17, 115, 1), (14, 46, 21), (29, 24, 19), (11, 15, 46)],
+    [(13, 115, 6), (14, 46, 23), (44, 24, 7), (59, 16, 1)],
+    [(12, 121, 7), (12, 47, 26), (39, 24, 14), (22, 15, 41)],
+    [(6, 121, 14), (6, 47, 34), (46, 24, 10), (2, 15, 64)],
+    [(17, 122, 4), (29, 46, 14), (49, 24, 10), (24, 15, 46)],
+    [(4, 122, 18), (13, 46, 32), (48, 24, 14), (42, 15, 32)],
+    [(20, 117, 4), (40, 47, 7), (43, 24, 22), (10, 15, 67)],
+    [(19, 118, 6), (18, 47, 31), (34, 24, 34), (20, 15, 61)],
+];
+
+/// Powers of 2 in GF(256) with the polynomial 0x11d, repeated once so sums of logs index directly
+const EXP: [u8; 512] = {
+    let mut exp = [0; 512];
+    let mut value: u16 = 1;
+    let mut index = 0;
+    while index < 512 {
+        exp[index] = value as u8;
+        value <<= 1;
+        if value & 0x100 != 0 {
+            value ^= 0x11d;
+        }
+        index += 1;
+    }
+    exp
+};
+
+const LOG: [u8; 256] = {
+    let mut log = [0; 256];
+    let mut index = 0;
+    while index < 255 {
+        log[EXP[index] as usize] = index as u8;
+        index += 1;
+    }
+    log
+};
+
+fn mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        EXP[LOG[a as usize] as usize + LOG[b as usize] as usize]
+    }
+}
+
+fn div(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        0
+    } else {
+        EXP[LOG[a as usize] as usize + 255 - LOG[b as usize] as usize]
+    }
+}
+
+/// 2 to the power of `exponent`, which may be negative
+fn power(exponent: i64) -> u8 {
+    EXP[exponent.rem_euclid(255) as usize]
+}
+
+/// Evaluate a polynomial whose coefficients start from the constant term
+fn evaluate(polynomial: &[u8], x: u8) -> u8 {
+    polynomial
+        .iter()
+        .rev()
+        .fold(0, |value, &coefficient| mul(value, x) ^ coefficient)
+}
+
+/// Correct a block of data and error correction codewords in place
+///
+/// Returns false when there are more errors than the block can correct.
+fn correct(block: &mut [u8], ec_codewords: usize) -> bool {
+    // 첫 코드워드가 최고차항
+    let syndromes = |block: &[u8]| -> Vec<u8> {
+        (0..ec_codewords)
+            .map(|index| {
+                let x = power(index as i64);
+                block
+                    .iter()
+                    .fold(0, |value, &codeword| mul(value, x) ^ codeword)
+            })
+            .collect()
+    };
+
+    let syndrome = syndromes(block);
+    if syndrome.iter().all(|&value| value == 0) {
+        return true;
+    }
+
+    // 벌리캠프-매시 알고리즘으로 오류 위치 다항식을 구함
+    let mut locator = vec![1u8];
+    let mut previous = vec![1u8];
+    let (mut errors, mut shift, mut previous_discrepancy) = (0, 1, 1u8);
+    for index in 0..ec_codewords {
+        let mut discrepancy = syndrome[index];
+        for (offset, &coefficient) in locator.iter().enumerate().skip(1) {
+            if offset <= index {
+                discrepancy ^= mul(coefficient, syndrome[index - offset]);
+            }
+        }
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+
+        let scale = div(discrepancy, previous_discrepancy);
+        let last = locator.clone();
+        if locator.len() < previous.len() + shift {
+            locator.resize(previous.len() + shift, 0);
+        }
+        for (offset, &coefficient) in previous.iter().enumerate() {
+            locator[offset + shift] ^= mul(scale, coefficient);
+        }
+
+        if errors * 2 <= index {
+            errors = index + 1 - errors;
+            previous = last;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+
+    if errors * 2 > ec_codewords {
+        return false;
+    }
+
+    // 치엔 탐색: 오류가 있는 차수 e에서 위치 다항식이 2^-e를 근으로 가짐
+    let length = block.len() as i64;
+    let positions: Vec<i64> = (0..length)
+        .filter(|&degree| evaluate(&locator, power(-degree)) == 0)
+        .collect();
+    if positions.len() != errors {
+        return false;
+    }
+
+    let mut evaluator = vec![0u8; ec_codewords];
+    for (i, &coefficient) in locator.iter().enumerate() {
+        for (j, &value) in syndrome.iter().enumerate() {
+            if i + j < ec_codewords {
+                evaluator[i + j] ^= mul(coefficient, value);
+            }
+        }
+    }
+
+    // 포니 알고리즘으로 오류의 크기를 구함
+    for &degree in &positions {
+        let inverse = power(-degree);
+        let derivative = locator.iter().enumerate().skip(1).step_by(2).fold(
+            0,
+            |value, (order, &coefficient)| {
+                value ^ mul(coefficient, power(-degree * (order as i64 - 1)))
+            },
+        );
+        if derivative == 0 {
+            return false;
+        }
+
+        let magnitude = mul(
+            power(degree),
+            div(evaluate(&evaluator, inverse), derivative),
+        );
+        block[(length - 1 - degree) as usize] ^= magnitude;
+    }
+
+    syndromes(block).iter().all(|&value| value == 0)
+}
+
+/// Split the interleaved codewords into blocks, correct them and join their data
+fn correct_blocks(codewords: &[u8], version: i16, level: usize) -> Option<Vec<u8>> {
+    let (short_blocks, short_size, long_blocks) = BLOCKS[version as usize - 1][level];
+    let ec_codewords = EC_CODEWORDS[version as usize - 1][level];
+    let sizes: Vec<usize> = std::iter::repeat_n(short_size, short_blocks)
+        .chain(std::iter::repeat_n(short_size + 1, long_blocks))
+        .collect();
+
+    let mut blocks: Vec<Vec<u8>> = sizes
+        .iter()
+        .map(|size| Vec::with_capacity(size + ec_codewords))
+        .collect();
+    let mut codewords = codewords.iter();
+    for index in 0..=short_size {
+        for (block, &size) in blocks.iter_mut().zip(&sizes) {
+            if index < size {
+                block.push(*codewords.next()?);
+            }
+        }
+    }
+    for _ in 0..ec_codewords {
+        for block in &mut blocks {
+            block.push(*codewords.next()?);
+        }
+    }
+
+    let mut data = vec![];
+    for (block, size) in blocks.iter_mut().zip(sizes) {
+        if !correct(block, ec_codewords) {
+            return None;
+        }
+        data.extend_from_slice(&block[..size]);
+    }
+
+    Some(data)
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn remaining(&self) -> usize {
+        self.data.len() * 8 - self.position
+    }
+
+    fn read(&mut self, count: usize) -> Option<u32> {
+        if count > self.remaining() {
+            return None;
+        }
+
+        let mut value = 0;
+        for _ in 0..count {
+            let bit = self.data[self.position / 8] >> (7 - self.position % 8) & 1;
+            value = value << 1 | bit as u32;
+            self.position += 1;
+        }
+
+        Some(value)
+    }
+}
+
+const ALPHANUMERIC: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// ECI designator of Shift JIS
+const ECI_SHIFT_JIS: u32 = 20;
+
+/// The text of the data segments
+fn parse_segments(data: &[u8], version: i16) -> Option<String> {
+    let mut reader = BitReader { data, position: 0 };
+    let size_class = match version {
+        1..=9 => 0,
+        10..=26 => 1,
+        _ => 2,
+    };
+
+    let mut text = String::new();
+    let mut bytes = vec![];
+    let mut eci = None;
+    // 바이트 구간은 여러 구간에 걸친 UTF-8이 있을 수 있어 모아서 해석
+    let flush = |bytes: &mut Vec<u8>, text: &mut String, eci: Option<u32>| {
+        if bytes.is_empty() {
+            return;
+        }
+        if eci == Some(ECI_SHIFT_JIS) {
+            text.push_str(&encoding_rs::SHIFT_JIS.decode_without_bom_handling(bytes).0);
+        } else {
+            match std::str::from_utf8(bytes) {
+                Ok(utf8) => text.push_str(utf8),
+                // 표준의 기본 인코딩은 ISO-8859-1
+                Err(_) => text.extend(bytes.iter().map(|&byte| byte as char)),
+            }
+        }
+        bytes.clear();
+    };
+
+    while reader.remaining() >= 4 {
+        match reader.read(4)? {
+            0b0000 => break,
+            0b0001 => {
+                flush(&mut bytes, &mut text, eci);
+                let mut count = reader.read([10, 12, 14][size_class])?;
+                while count > 0 {
+                    let digits = count.min(3);
+                    let value = reader.read([0, 4, 7, 10][digits as usize])?;
+                    if value >= 10u32.pow(digits) {
+                        return None;
+                    }
+                    text.push_str(&format!("{:0width$}", value, width = digits as usize));
+                    count -= digits;
+                }
+            }
+            0b0010 => {
+                flush(&mut bytes, &mut text, eci);
+                let mut count = reader.read([9, 11, 13][size_class])?;
+                while count > 0 {
+                    let characters = count.min(2);
+                    let value = reader.read(if characters == 2 { 11 } else { 6 })? as usize;
+                    if characters == 2 {
+                        text.push(*ALPHANUMERIC.get(value / 45)? as char);
+                    }
+                    text.push(*ALPHANUMERIC.get(value % 45)? as char);
+                    count -= characters;
+                }
+            }
+            0b0100 => {
+                let count = reader.read([8, 16, 16][size_class])?;
+                for _ in 0..count {
+                    bytes.push(reader.read(8)? as u8);
+                }
+            }
+            0b1000 => {
+                flush(&mut bytes, &mut text, eci);
+                let count = reader.read([8, 10, 12][size_class])?;
+                let mut shift_jis = vec![];
+                for _ in 0..count {
+                    let value = reader.read(13)?;
+                    let value = ((value / 0xc0) << 8) | (value % 0xc0);
+                    let value = value + if value < 0x1f00 { 0x8140 } else { 0xc140 };
+                    shift_jis.extend([(value >> 8) as u8, value as u8]);
+                }
+                text.push_str(
+                    &encoding_rs::SHIFT_JIS
+                        .decode_without_bom_handling(&shift_jis)
+                        .0,
+                );
+            }
+            0b0111 => {
+                flush(&mut bytes, &mut text, eci);
+                let first = reader.read(8)?;
+                eci = Some(if first & 0x80 == 0 {
+                    first
+                } else if first & 0xc0 == 0x80 {
+                    (first & 0x3f) << 8 | reader.read(8)?
+                } else {
+                    (first & 0x1f) << 16 | reader.read(16)?
+                });
+            }
+            // 구조적 연결 정보는 건너뜀
+            0b0011 => {
+                reader.read(16)?;
+            }
+            0b0101 => {}
+            0b1001 => {
+                reader.read(8)?;
+            }
+            _ => return None,
+        }
+    }
+    flush(&mut bytes, &mut text, eci);
+
+    Some(text)
+}
+
+fn decode_grid(grid: &Grid, version: i16) -> Option<Decoded> {
+    let (level, mask) = read_format(grid)?;
+    let codewords = read_codewords(grid, version, mask);
+    let data = correct_blocks(&codewords, version, level)?;
+    let text = parse_segments(&data, version)?;
+
+    Some(Decoded {
+        text,
+        version,
+        level: [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H][level],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{imageops, Luma};
+    use qrcode::{Color, QrCode};
+
+    /// The code drawn with a quiet zone of 4 modules, `scale` pixels per module
+    fn render(text: &str, version: i16, level: EcLevel, scale: u32) -> GrayImage {
+        let code = QrCode::with_version(text, Version::Normal(version), level).unwrap();
+        let width = code.width() as u32;
+        let colors = code.to_colors();
+        let size = (width + 8) * scale;
+
+        GrayImage::from_fn(size, size, |x, y| {
+            let (x, y) = ((x / scale) as i64 - 4, (y / scale) as i64 - 4);
+            let inside = (0..width as i64).contains(&x) && (0..width as i64).contains(&y);
+            if inside && colors[(y * width as i64 + x) as usize] == Color::Dark {
+                Luma([20])
+            } else {
+                Luma([235])
+            }
+        })
+    }
+
+    /// Rotate by any angle around the center, filling the corners with the background
+    fn rotate(image: &GrayImage, degrees: f64) -> GrayImage {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let size = (image.width().max(image.height()) as f64 * 1.5) as u32;
+        let (cx, cy) = (image.width() as f64 / 2.0, image.height() as f64 / 2.0);
+        let center = size as f64 / 2.0;
+
+        GrayImage::from_fn(size, size, |x, y| {
+            let (dx, dy) = (x as f64 - center, y as f64 - center);
+            let (sx, sy) = (cx + dx * cos + dy * sin, cy - dx * sin + dy * cos);
+            if sx >= 0.0 && sy >= 0.0 && (sx as u32) < image.width() && (sy as u32) < image.height()
+            {
+                *image.get_pixel(sx as u32, sy as u32)
+            } else {
+                Luma([235])
+            }
+        })
+    }
+
+    fn texts(image: &GrayImage) -> Vec<String> {
+        let mut texts: Vec<String> = decode(image).into_iter().map(|code| code.text).collect();
+        texts.sort();
+        texts
+    }
+
+    /// Pseudo-random bytes, so the tests are the same every run
+    fn noise(seed: u64, count: usize) -> Vec<u8> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 24) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn versions_and_levels() {
+        for version in [1, 2, 5, 7, 10] {
+            for level in [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H] {
+                let image = render("HELLO 123", version, level, 4);
+                let decoded = decode(&image);
+
+                assert_eq!(decoded.len(), 1, "version {} {:?}", version, level);
+                assert_eq!(decoded[0].text, "HELLO 123");
+                assert_eq!(decoded[0].version, version);
+                assert_eq!(decoded[0].level, level);
+            }
+        }
+    }
+
+    #[test]
+    fn byte_mode_text() {
+        let text = "https://example.com/?q=안녕하세요&n=42";
+        let code = QrCode::new(text).unwrap();
+        let version = match code.version() {
+            Version::Normal(version) => version,
+            Version::Micro(_) => unreachable!(),
+        };
+
+        assert_eq!(
+            texts(&render(text, version, code.error_correction_level(), 3)),
+            [text]
+        );
+    }
+
+    #[test]
+    fn rotated() {
+        let image = render("rotated", 3, EcLevel::M, 5);
+        assert_eq!(texts(&imageops::rotate90(&image)), ["rotated"]);
+        assert_eq!(texts(&imageops::rotate180(&image)), ["rotated"]);
+        for degrees in [15.0, 30.0, 45.0, 200.0] {
+            assert_eq!(texts(&rotate(&image, degrees)), ["rotated"], "{}°", degrees);
+        }
+    }
+
+    #[test]
+    fn inverted() {
+        let mut image = render("inverted", 2, EcLevel::Q, 4);
+        imageops::invert(&mut image);
+        assert_eq!(texts(&image), ["inverted"]);
+    }
+
+    #[test]
+    fn scaled() {
+        for scale in [2, 3, 7, 12] {
+            let image = render("scaled", 4, EcLevel::L, scale);
+            assert_eq!(texts(&image), ["scaled"], "scale {}", scale);
+        }
+
+        // 모듈이 정수 픽셀이 아니도록 크기를 바꿔봄
+        let image = render("resized", 4, EcLevel::L, 4);
+        let resized = imageops::resize(&image, 237, 237, imageops::FilterType::Triangle);
+        assert_eq!(texts(&resized), ["resized"]);
+    }
+
+    #[test]
+    fn two_codes() {
+        let first = render("first", 2, EcLevel::M, 4);
+        let second = render("second", 3, EcLevel::H, 4);
+        let mut image = GrayImage::from_pixel(
+            first.width() + second.width(),
+            first.height().max(second.height()),
+            Luma([235]),
+        );
+        imageops::replace(&mut image, &first, 0, 0);
+        imageops::replace(&mut image, &second, first.width() as i64, 0);
+
+        assert_eq!(texts(&image), ["first", "second"]);
+    }
+
+    #[test]
+    fn noise_does_not_panic() {
+        for (seed, size) in [(1, 64), (2, 200), (3, 401), (4, 1)] {
+            let pixels = noise(seed, (size * size) as usize);
+            let image = GrayImage::from_raw(size, size, pixels).unwrap();
+            decode(&image);
+        }
+
+        // 잡음 위에 찍힌 코드
+        let mut image = render("noisy", 2, EcLevel::H, 4);
+        let pixels = noise(5, (image.width() * image.height()) as usize);
+        for (pixel, noise) in image.pixels_mut().zip(pixels) {
+            if noise < 8 {
+                pixel[0] = 255 - pixel[0];
+            }
+        }
+        decode(&image);
+
+        decode(&GrayImage::new(0, 0));
+        decode(&GrayImage::from_pixel(50, 50, Luma([0])));
+    }
+
+    #[test]
+    fn many_finder_like_patterns() {
+        // 작은 파인더 모양을 빽빽하게 깔아 후보 수가 상한에 걸리게 함
+        let finder = render("x", 1, EcLevel::L, 1);
+        let finder = imageops::crop_imm(&finder, 4, 4, 8, 8).to_image();
+        let mut image = GrayImage::from_pixel(800, 800, Luma([235]));
+        for y in (0..800).step_by(8) {
+            for x in (0..800).step_by(8) {
+                imageops::replace(&mut image, &finder, x, y);
+            }
+        }
+
+        decode(&image);
+    }
+
+    #[test]
+    fn truncated_does_not_panic() {
+        let image = render("truncated", 5, EcLevel::M, 4);
+        for (width, height) in [
+            (image.width() / 2, image.height()),
+            (image.width(), 40),
+            (30, 30),
+        ] {
+            let cropped = imageops::crop_imm(&image, 0, 0, width, height).to_image();
+            decode(&cropped);
+            let cropped =
+                imageops::crop_imm(&image, image.width() - width, 0, width, height).to_image();
+            decode(&cropped);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Reduce the pixels of an image to a few representative colors
+//!
+//! Both quantizers return the colors with the share of pixels they stand for,
+//! from the most common to the least common.
+
+use crate::color::Color;
+use rand::Rng;
+
+const MAX_ITERATIONS: usize = 30;
+
+fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+fn nearest(point: [f64; 3], centroids: &[[f64; 3]]) -> usize {
+    (0..centroids.len())
+        .min_by(|&a, &b| distance(point, centroids[a]).total_cmp(&distance(point, centroids[b])))
+        .unwrap()
+}
+
+fn sorted(mut colors: Vec<(Color, f64)>) -> Vec<(Color, f64)> {
+    colors.sort_by(|a, b| b.1.total_cmp(&a.1));
+    colors
+}
+
+/// K-means clustering in OKLab, so that clusters follow perceived differences
+pub fn kmeans(pixels: &[Color], k: usize, rng: &mut impl Rng) -> Vec<(Color, f64)> {
+    if pixels.is_empty() || k == 0 {
+        return vec![];
+    }
+
+    let points: Vec<[f64; 3]> = pixels
+        .iter()
+        .map(|color| {
+            let (l, a, b) = color.to_oklab();
+            [l, a, b]
+        })
+        .collect();
+
+    // k-means++ 초기화: 이미 고른 중심에서 먼 점일수록 뽑힐 확률이 높음
+    let mut centroids = vec![points[rng.gen_range(0..points.len())]];
+    let mut distances: Vec<f64> = points
+        .iter()
+        .map(|&point| distance(point, centroids[0]))
+        .collect();
+    while centroids.len() < k {
+        let total: f64 = distances.iter().sum();
+        if total <= 0.0 {
+            // 서로 다른 색이 k개보다 적음
+            break;
+        }
+
+        let mut target = rng.gen_range(0.0..total);
+        let index = distances
+            .iter()
+            .position(|&d| {
+                target -= d;
+                target < 0.0
+            })
+            .unwrap_or(points.len() - 1);
+        centroids.push(points[index]);
+
+        for (d, &point) in distances.iter_mut().zip(&points) {
+            *d = d.min(distance(point, points[index]));
+        }
+    }
+
+    let mut assignments = vec![usize::MAX; points.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (assignment, &point) in assignments.iter_mut().zip(&points) {
+            let cluster = nearest(point, &centroids);
+            if *assignment != cluster {
+                *assignment = cluster;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![([0.0; 3], 0usize); centroids.len()];
+        for (&cluster, point) in assignments.iter().zip(&points) {
+            let (sum, count) = &mut sums[cluster];
+            for channel in 0..3 {
+                sum[channel] += point[channel];
+            }
+            *count += 1;
+        }
+        for (centroid, (sum, count)) in centroids.iter_mut().zip(sums) {
+            if count > 0 {
+                *centroid = sum.map(|value| value / count as f64);
+            }
+        }
+    }
+
+    let mut counts = vec![0usize; centroids.len()];
+    for &cluster in &assignments {
+        counts[cluster] += 1;
+    }
+
+    sorted(
+        centroids
+            .iter()
+            .zip(counts)
+            .filter(|&(_, count)| count > 0)
+            .map(|(centroid, count)| {
+                (
+                    Color::from_oklab(centroid[0], centroid[1], centroid[2]),
+                    count as f64 / points.len() as f64,
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Median cut: split the box with the widest channel range at its median
+/// until there are `k` boxes, then average each box
+pub fn median_cut(pixels: &[Color], k: usize) -> Vec<(Color, f64)> {
+    if pixels.is_empty() || k == 0 {
+        return vec![];
+    }
+
+    let range = |pixels: &[[u8; 3]], channel: usize| {
+        let values = pixels.iter().map(|pixel| pixel[channel]);
+        values.clone().max().unwrap() - values.min().unwrap()
+    };
+    let widest = |pixels: &[[u8; 3]]| {
+        (0..3)
+            .map(|channel| (channel, range(pixels, channel)))
+            .max_by_key(|&(_, range)| range)
+            .unwrap()
+    };
+
+    let total = pixels.len();
+    let mut boxes: Vec<Vec<[u8; 3]>> = vec![pixels.iter().map(|c| [c.r, c.g, c.b]).collect()];
+    while boxes.len() < k {
+        let Some((index, channel)) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, pixels)| pixels.len() > 1)
+            .map(|(index, pixels)| (index, widest(pixels)))
+            .filter(|&(_, (_, range))| range > 0)
+            .max_by_key(|&(_, (_, range))| range)
+            .map(|(index, (channel, _))| (index, channel))
+        else {
+            break;
+        };
+
+        let mut pixels = boxes.swap_remove(index);
+        pixels.sort_unstable_by_key(|pixel| pixel[channel]);
+        // 같은 값이 양쪽으로 나뉘지 않도록 중앙값 경계에서 자름
+        let median = pixels[pixels.len() / 2][channel];
+        let mut split = pixels.partition_point(|pixel| pixel[channel] < median);
+        if split == 0 {
+            split = pixels.partition_point(|pixel| pixel[channel] <= median);
+        }
+        let upper = pixels.split_off(split);
+        boxes.push(pixels);
+        boxes.push(upper);
+    }
+
+    sorted(
+        boxes
+            .iter()
+            .map(|pixels| {
+                let mut sum = [0u64; 3];
+                for pixel in pixels {
+                    for channel in 0..3 {
+                        sum[channel] += pixel[channel] as u64;
+                    }
+                }
+                let average = sum.map(|value| (value as f64 / pixels.len() as f64).round() as u8);
+
+                (
+                    Color::rgb(average[0], average[1], average[2]),
+                    pixels.len() as f64 / total as f64,
+                )
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,48 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Seed of a random command, shown back to the user so the result can be made again
+pub struct Seed {
+    text: String,
+    bytes: [u8; 32],
+}
+
+impl Seed {
+    /// Any text is accepted, numbers are written in their shortest form first
+    /// so that `042` and `42` give the same result
+    pub fn new(text: &str) -> Self {
+        let text = text.trim();
+        let text = match text.parse::<u64>() {
+            Ok(number) => number.to_string(),
+            Err(_) => text.to_string(),
+        };
+        let bytes = Sha256::digest(text.as_bytes()).into();
+
+        Seed { text, bytes }
+    }
+
+    /// A new random seed, for when the user didn't give one
+    pub fn random() -> Self {
+        Self::new(&rand::random::<u64>().to_string())
+    }
+
+    /// The user's seed if there is one, otherwise a random one
+    pub fn from_option(text: Option<&str>) -> Self {
+        match text {
+            Some(text) if !text.trim().is_empty() => Self::new(text),
+            _ => Self::random(),
+        }
+    }
+
+    pub fn rng(&self) -> ChaCha8Rng {
+        ChaCha8Rng::from_seed(self.bytes)
+    }
+}
+
+impl fmt::Display for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}